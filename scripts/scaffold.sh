@@ -19,12 +19,14 @@ module_path="src/solutions/$filename.rs";
 touch $module_path;
 
 cat > $module_path <<EOF
-pub fn part_one(input: &str) -> u32 {
-    0
+use crate::error::SolveResult;
+
+pub fn try_part_one(input: &str) -> SolveResult<u32> {
+    Ok(0)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    0
+pub fn try_part_two(input: &str) -> SolveResult<u32> {
+    Ok(0)
 }
 
 #[cfg(test)]
@@ -33,16 +35,16 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", day);
-        assert_eq!(part_one(&input), 0);
+        assert_eq!(try_part_one(&input), Ok(0));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", day);
-        assert_eq!(part_two(&input), 0);
+        assert_eq!(try_part_two(&input), Ok(0));
     }
 }
 EOF
@@ -58,7 +60,7 @@ touch $example_path;
 echo "Created example file \"$example_path\"";
 
 line="        $day => solve_day!($filename, &input),"
-perl -pi -le "print '$line' if(/^*.day not solved/);" "src/main.rs";
+perl -pi -le "print '$line' if(/^\s*_ => \{/);" "src/main.rs";
 
 echo "Linked new module in \"src/main.rs\"";
 
@@ -0,0 +1,47 @@
+use crate::helpers::parse::ParseError;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// error returned by solutions when they can not produce an answer for an input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// the input does not match the format of the puzzle.
+    Parse(ParseError),
+    /// the input is well-formed, but breaks an assumption of the puzzle.
+    InvalidInput(String),
+    /// the input is valid, but the solution could not find an answer for it.
+    NoSolution(String),
+    /// an observer stopped the simulation before it finished, see `helpers::trace`.
+    Stopped,
+}
+
+impl SolveError {
+    pub fn invalid(reason: impl Into<String>) -> Self {
+        SolveError::InvalidInput(reason.into())
+    }
+
+    pub fn no_solution(reason: impl Into<String>) -> Self {
+        SolveError::NoSolution(reason.into())
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::Parse(err) => write!(f, "could not parse input: {}", err),
+            SolveError::InvalidInput(reason) => write!(f, "invalid input: {}", reason),
+            SolveError::NoSolution(reason) => write!(f, "no solution: {}", reason),
+            SolveError::Stopped => write!(f, "stopped by observer"),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+impl From<ParseError> for SolveError {
+    fn from(err: ParseError) -> Self {
+        SolveError::Parse(err)
+    }
+}
+
+pub type SolveResult<T> = Result<T, SolveError>;
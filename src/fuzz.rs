@@ -0,0 +1,251 @@
+//! Fuzz targets for the parsers and the solutions.
+//! Parsers are expected to return an error for malformed input instead of panicking. Every target in `PARSERS`
+//! feeds raw bytes straight to a single parser, `fuzz` runs both parts of a day on normalized input.
+//! `mutate` derives malformed inputs from well-formed ones.
+
+use crate::helpers::parse::normalize;
+use crate::helpers::rng::Rng;
+use crate::solutions::*;
+
+/// a fuzz target, takes arbitrary bytes and must not panic.
+pub type Target = fn(&[u8]);
+
+/// the parser targets, named after the function they feed.
+pub const PARSERS: [(&str, Target); 5] = [
+    ("day10::parse", navigation_line),
+    ("day16::decoder::decode", transmission),
+    ("day18::from_str", snail_number),
+    ("day19::parse", scanner_reports),
+    ("day22::parse", reboot_steps),
+];
+
+/// bytes that are not valid UTF-8 are replaced, so every input reaches the parser.
+fn text(data: &[u8]) -> std::borrow::Cow<'_, str> {
+    String::from_utf8_lossy(data)
+}
+
+/// Feed arbitrary bytes to the bracket parser of day 10 as a single line.
+pub fn navigation_line(data: &[u8]) {
+    let _ = day10::parse(&text(data));
+}
+
+/// Feed arbitrary bytes to both BITS decoders of day 16.
+pub fn transmission(data: &[u8]) {
+    let message = text(data);
+    let _ = day16::decoder::decode(&message);
+    let _ = day16::decoder::decode_strict(&message);
+}
+
+/// Feed arbitrary bytes to the snail number parser of day 18 as a single line.
+pub fn snail_number(data: &[u8]) {
+    let _ = day18::from_str(&text(data));
+}
+
+/// Feed arbitrary bytes to the scanner report parser of day 19.
+pub fn scanner_reports(data: &[u8]) {
+    let _ = day19::parse(&text(data));
+}
+
+/// Feed arbitrary bytes to the reboot step parser of day 22.
+pub fn reboot_steps(data: &[u8]) {
+    let _ = day22::parse(&text(data));
+}
+
+macro_rules! fuzz_day {
+    ($day:path, $input:expr) => {{
+        use $day::*;
+        let _ = try_part_one($input);
+        let _ = try_part_two($input);
+    }};
+}
+
+/// Feed arbitrary bytes to both parts of a day, from parsing to the answer.
+/// Returns `false` if there is no solution for `day`.
+/// Bytes that are not valid UTF-8 are skipped, like they would be when reading an input file.
+/// The input is normalized the same way `read_file` does it.
+pub fn fuzz(day: u8, data: &[u8]) -> bool {
+    let input = match std::str::from_utf8(data) {
+        Ok(input) => normalize(input),
+        Err(_) => return (1..=25).contains(&day),
+    };
+    let input = input.as_str();
+
+    match day {
+        1 => fuzz_day!(day01, input),
+        2 => fuzz_day!(day02, input),
+        3 => fuzz_day!(day03, input),
+        4 => fuzz_day!(day04, input),
+        5 => fuzz_day!(day05, input),
+        6 => fuzz_day!(day06, input),
+        7 => fuzz_day!(day07, input),
+        8 => fuzz_day!(day08, input),
+        9 => fuzz_day!(day09, input),
+        10 => fuzz_day!(day10, input),
+        11 => fuzz_day!(day11, input),
+        12 => fuzz_day!(day12, input),
+        13 => fuzz_day!(day13, input),
+        14 => fuzz_day!(day14, input),
+        15 => fuzz_day!(day15, input),
+        16 => fuzz_day!(day16, input),
+        17 => fuzz_day!(day17, input),
+        18 => fuzz_day!(day18, input),
+        19 => fuzz_day!(day19, input),
+        20 => fuzz_day!(day20, input),
+        21 => fuzz_day!(day21, input),
+        22 => fuzz_day!(day22, input),
+        23 => fuzz_day!(day23, input),
+        24 => fuzz_day!(day24, input),
+        25 => fuzz_day!(day25, input),
+        _ => return false,
+    }
+
+    true
+}
+
+/// Apply a few random edits to `data`: deleting, replacing, inserting or duplicating bytes and truncating.
+/// Inserted bytes are mostly taken from `data` itself, so mutants stay close to the original format.
+pub fn mutate(rng: &mut Rng, data: &[u8]) -> Vec<u8> {
+    let mut data = data.to_vec();
+
+    for _ in 0..=rng.below(3) {
+        let byte = |rng: &mut Rng, data: &[u8]| {
+            if data.is_empty() || rng.chance(0.2) {
+                rng.below(256) as u8
+            } else {
+                *rng.choose(data)
+            }
+        };
+
+        match rng.below(5) {
+            0 if !data.is_empty() => {
+                data.remove(rng.below(data.len()));
+            }
+            1 if !data.is_empty() => {
+                let i = rng.below(data.len());
+                data[i] = byte(rng, &data);
+            }
+            2 => {
+                let b = byte(rng, &data);
+                data.insert(rng.below(data.len() + 1), b);
+            }
+            3 if !data.is_empty() => {
+                let from = rng.below(data.len());
+                let to = (from + rng.below(16) + 1).min(data.len());
+                let copy = data[from..to].to_vec();
+                let at = rng.below(data.len() + 1);
+                data.splice(at..at, copy);
+            }
+            _ => data.truncate(rng.below(data.len() + 1)),
+        }
+    }
+
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::generate;
+    use std::fs;
+    use std::panic;
+
+    fn assert_no_panic(day: u8, data: &[u8]) {
+        let result = panic::catch_unwind(|| fuzz(day, data));
+        assert!(
+            result.is_ok(),
+            "day {} panicked for input {:?}",
+            day,
+            String::from_utf8_lossy(data)
+        );
+    }
+
+    fn assert_parser_no_panic(name: &str, parser: Target, data: &[u8]) {
+        let result = panic::catch_unwind(|| parser(data));
+        assert!(
+            result.is_ok(),
+            "{} panicked for input {:?}",
+            name,
+            String::from_utf8_lossy(data)
+        );
+    }
+
+    #[test]
+    fn test_mutated_parser_inputs() {
+        let mut rng = Rng::new(2);
+
+        for (name, parser) in PARSERS {
+            let day = name[3..5].parse().unwrap();
+            let mut seeds: Vec<String> = generate(day, 4, 0).into_iter().collect();
+            // parsers of single lines get a single line, and day 16 has no example file.
+            if name.starts_with("day10") || name.starts_with("day18") {
+                seeds = seeds[0].lines().map(String::from).collect();
+            } else {
+                seeds.extend(fs::read_to_string(format!(
+                    "src/examples/day{:02}.txt",
+                    day
+                )));
+            }
+
+            for seed in &seeds {
+                // raw input is not normalized, so line endings and trailing whitespace reach the parser too.
+                let crlf = seed.replace('\n', "\r\n") + " ";
+                for data in [seed.as_bytes(), crlf.as_bytes()] {
+                    assert_parser_no_panic(name, parser, data);
+                    for _ in 0..100 {
+                        assert_parser_no_panic(name, parser, &mutate(&mut rng, data));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_parser_bytes() {
+        let mut rng = Rng::new(3);
+
+        for (name, parser) in PARSERS {
+            assert_parser_no_panic(name, parser, b"");
+            assert_parser_no_panic(name, parser, b"\r\n\xff");
+
+            for _ in 0..100 {
+                let data: Vec<u8> = (0..rng.below(64)).map(|_| rng.below(256) as u8).collect();
+                assert_parser_no_panic(name, parser, &data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mutated_inputs() {
+        let mut rng = Rng::new(0);
+
+        for day in 1..=25 {
+            // not every day has an example file, e.g. day 16 is tested with inline messages.
+            let example = fs::read_to_string(format!("src/examples/day{:02}.txt", day));
+            let mut seeds: Vec<String> = example.into_iter().collect();
+            seeds.extend(generate(day, 4, 0));
+
+            for seed in &seeds {
+                for _ in 0..100 {
+                    assert_no_panic(day, &mutate(&mut rng, seed.as_bytes()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_bytes() {
+        let mut rng = Rng::new(1);
+
+        for day in 1..=25 {
+            assert_no_panic(day, b"");
+            assert_no_panic(day, b"\n\n");
+
+            for _ in 0..20 {
+                let data: Vec<u8> = (0..rng.below(64)).map(|_| rng.below(256) as u8).collect();
+                assert_no_panic(day, &data);
+            }
+        }
+
+        assert!(!fuzz(26, b""));
+    }
+}
@@ -0,0 +1,738 @@
+//! Generators for random but valid puzzle inputs.
+//! Every generator takes a seeded `Rng`, so the same seed always produces the same input.
+//! `size` scales the input (e.g. number of lines or width of a grid) and is clamped to what a day can handle.
+
+use crate::helpers::rng::Rng;
+use crate::solutions::day16::decoder::{Header, LengthType, Literal, Operator, Packet};
+use crate::solutions::day16::encoder::encode;
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+
+/// Generate an input for `day`. Returns `None` for days that do not exist.
+pub fn generate(day: u8, size: usize, seed: u64) -> Option<String> {
+    let mut rng = Rng::new(seed);
+    let rng = &mut rng;
+
+    let input = match day {
+        1 => day01(rng, size),
+        2 => day02(rng, size),
+        3 => day03(rng, size),
+        4 => day04(rng, size),
+        5 => day05(rng, size),
+        6 => day06(rng, size),
+        7 => day07(rng, size),
+        8 => day08(rng, size),
+        9 => day09(rng, size),
+        10 => day10(rng, size),
+        11 => day11(rng, size),
+        12 => day12(rng, size),
+        13 => day13(rng, size),
+        14 => day14(rng, size),
+        15 => day15(rng, size),
+        16 => day16(rng, size),
+        17 => day17(rng, size),
+        18 => day18(rng, size),
+        19 => day19(rng, size).input,
+        20 => day20(rng, size),
+        21 => day21(rng, size),
+        22 => day22(rng, size),
+        23 => day23(rng, size),
+        24 => day24(rng, size),
+        25 => day25(rng, size),
+        _ => return None,
+    };
+
+    Some(input)
+}
+
+/// join lines into an input with a trailing newline.
+fn lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().map(|l| l + "\n").collect()
+}
+
+fn grid(rng: &mut Rng, width: usize, height: usize, cell: impl Fn(&mut Rng) -> char) -> String {
+    lines((0..height).map(|_| (0..width).map(|_| cell(rng)).collect()))
+}
+
+/// `size` sonar depths.
+pub fn day01(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100..=1000);
+    lines((0..size.max(1)).map(|_| {
+        depth = (depth + rng.range(-20..=40)).max(0);
+        depth.to_string()
+    }))
+}
+
+/// `size` submarine commands.
+pub fn day02(rng: &mut Rng, size: usize) -> String {
+    lines((0..size.max(1)).map(|_| {
+        let direction = rng.choose(&["forward", "down", "up"]);
+        format!("{} {}", direction, rng.range(1..=9))
+    }))
+}
+
+/// `size` 12-bit diagnostic numbers.
+pub fn day03(rng: &mut Rng, size: usize) -> String {
+    grid(rng, 12, size.max(1), |rng| *rng.choose(&['0', '1']))
+}
+
+/// a draw of the numbers `0..100` and `size` bingo boards. every board wins eventually.
+pub fn day04(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<u32> = (0..100).collect();
+    rng.shuffle(&mut numbers);
+
+    let mut input = numbers.iter().join(",") + "\n";
+
+    for _ in 0..size.max(1) {
+        rng.shuffle(&mut numbers);
+        input.push('\n');
+        for row in numbers[..25].chunks(5) {
+            writeln!(
+                input,
+                "{}",
+                row.iter().map(|n| format!("{:>2}", n)).join(" ")
+            )
+            .unwrap();
+        }
+    }
+
+    input
+}
+
+/// `size` horizontal, vertical and diagonal vent lines on a `1000x1000` map.
+pub fn day05(rng: &mut Rng, size: usize) -> String {
+    lines((0..size.max(1)).map(|_| {
+        let (x, y) = (rng.range(0..=999), rng.range(0..=999));
+        let (dx, dy) = *rng.choose(&[
+            (1, 0),
+            (-1, 0),
+            (0, 1),
+            (0, -1),
+            (1, 1),
+            (1, -1),
+            (-1, 1),
+            (-1, -1),
+        ]);
+
+        // the longest line in this direction that stays on the map.
+        let reach = |p: i64, d: i64| match d {
+            1 => 999 - p,
+            -1 => p,
+            _ => i64::MAX,
+        };
+        let len = rng.range(0..=reach(x, dx).min(reach(y, dy)).min(300));
+
+        format!("{},{} -> {},{}", x, y, x + dx * len, y + dy * len)
+    }))
+}
+
+/// `size` lanternfish timers.
+pub fn day06(rng: &mut Rng, size: usize) -> String {
+    let timers = (0..size.max(1)).map(|_| rng.range(0..=8)).join(",");
+    timers + "\n"
+}
+
+/// `size` crab positions.
+pub fn day07(rng: &mut Rng, size: usize) -> String {
+    let positions = (0..size.max(1)).map(|_| rng.range(0..=1999)).join(",");
+    positions + "\n"
+}
+
+static DIGIT_SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// `size` displays with randomly crossed wires.
+pub fn day08(rng: &mut Rng, size: usize) -> String {
+    lines((0..size.max(1)).map(|_| {
+        let mut wires: Vec<char> = ('a'..='g').collect();
+        rng.shuffle(&mut wires);
+
+        let pattern = |rng: &mut Rng, digit: usize| {
+            let mut segments: Vec<char> = DIGIT_SEGMENTS[digit]
+                .chars()
+                .map(|c| wires[c as usize - 'a' as usize])
+                .collect();
+            rng.shuffle(&mut segments);
+            segments.into_iter().collect::<String>()
+        };
+
+        let mut digits: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut digits);
+
+        let signal = digits.iter().map(|d| pattern(rng, *d)).join(" ");
+        let outputs = (0..4)
+            .map(|_| {
+                let digit = rng.below(10);
+                pattern(rng, digit)
+            })
+            .join(" ");
+
+        format!("{} | {}", signal, outputs)
+    }))
+}
+
+/// a heightmap of at least `11x3` cells. every fourth column is a ridge, so there are at least three basins.
+pub fn day09(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(11);
+    let height = size.max(3);
+
+    lines((0..height).map(|_| {
+        (0..width)
+            .map(|x| {
+                if x % 4 == 3 || rng.chance(0.15) {
+                    '9'
+                } else {
+                    char::from_digit(rng.below(9) as u32, 10).unwrap()
+                }
+            })
+            .collect()
+    }))
+}
+
+static BRACKETS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+/// `size` lines of brackets. some are corrupted, the others (at least the first one) incomplete.
+pub fn day10(rng: &mut Rng, size: usize) -> String {
+    lines((0..size.max(1)).map(|i| {
+        let mut line = String::new();
+        let mut stack = Vec::new();
+
+        for _ in 0..rng.range(10..=60) {
+            // keep the stack shallow so completion scores fit into a `u64`.
+            if stack.is_empty() || (stack.len() < 20 && rng.chance(0.6)) {
+                let (open, close) = *rng.choose(&BRACKETS);
+                line.push(open);
+                stack.push(close);
+            } else {
+                line.push(stack.pop().unwrap());
+            }
+        }
+
+        if stack.is_empty() {
+            line.push('(');
+            stack.push(')');
+        }
+
+        if i > 0 && rng.chance(0.5) {
+            let expected = *stack.last().unwrap();
+            let (_, wrong) = *rng.choose(
+                &BRACKETS
+                    .iter()
+                    .filter(|(_, c)| *c != expected)
+                    .collect::<Vec<_>>(),
+            );
+            line.push(*wrong);
+        }
+
+        line
+    }))
+}
+
+/// a `10x10` grid of octopus energy levels. `size` is ignored.
+/// unlike puzzle inputs, random grids do not always synchronize.
+pub fn day11(rng: &mut Rng, _size: usize) -> String {
+    grid(rng, 10, 10, |rng| {
+        char::from_digit(rng.below(10) as u32, 10).unwrap()
+    })
+}
+
+/// name a cave by its index, e.g. `0 => "aa"`.
+fn cave_name(i: usize, big: bool) -> String {
+    let name: String = [i / 26 % 26, i % 26]
+        .iter()
+        .map(|c| (b'a' + *c as u8) as char)
+        .collect();
+
+    if big {
+        name.to_uppercase()
+    } else {
+        name
+    }
+}
+
+/// a cave system with `size` small caves (at most 6) and two big caves.
+/// big caves are never connected to each other and `start` connects to `end` via the first big cave.
+pub fn day12(rng: &mut Rng, size: usize) -> String {
+    let small: Vec<String> = (0..size.clamp(2, 6)).map(|i| cave_name(i, false)).collect();
+    let big: Vec<String> = (0..2).map(|i| cave_name(i, true)).collect();
+
+    let mut edges: HashSet<(String, String)> = HashSet::new();
+    let mut connect = |a: &str, b: &str| {
+        if a != b && !edges.contains(&(b.to_string(), a.to_string())) {
+            edges.insert((a.to_string(), b.to_string()));
+        }
+    };
+
+    connect("start", &big[0]);
+    connect(&big[0], "end");
+    connect("start", rng.choose(&small).as_str());
+    connect(rng.choose(&small).as_str(), "end");
+
+    for cave in &big {
+        for s in &small {
+            if rng.chance(0.5) {
+                connect(cave, s);
+            }
+        }
+    }
+
+    for _ in 0..small.len() / 2 {
+        let (a, b) = (rng.choose(&small), rng.choose(&small));
+        connect(a, b);
+    }
+
+    lines(
+        edges
+            .into_iter()
+            .sorted()
+            .map(|(a, b)| format!("{}-{}", a, b)),
+    )
+}
+
+/// `size` dots on a transparent sheet that is folded three times along each axis into an `40x6` code.
+pub fn day13(rng: &mut Rng, size: usize) -> String {
+    let unfold = |len: usize| {
+        (0..3).fold(vec![len], |mut acc, _| {
+            acc.push(acc.last().unwrap() * 2 + 1);
+            acc
+        })
+    };
+
+    // fold lines are the centers of every intermediate size, from the outermost inwards.
+    let widths = unfold(40);
+    let heights = unfold(6);
+    let x_folds: Vec<usize> = widths[1..].iter().rev().map(|w| w / 2).collect();
+    let y_folds: Vec<usize> = heights[1..].iter().rev().map(|h| h / 2).collect();
+
+    let mut dots = Vec::new();
+    while dots.len() < size.max(1) {
+        let x = rng.below(*widths.last().unwrap());
+        let y = rng.below(*heights.last().unwrap());
+        // dots never lie on a fold line.
+        if !x_folds.contains(&x) && !y_folds.contains(&y) {
+            dots.push(format!("{},{}", x, y));
+        }
+    }
+
+    // interleave the folds of both axes, keeping their order.
+    let (mut x, mut y) = (x_folds.iter(), y_folds.iter());
+    let mut folds = Vec::new();
+    while x.len() + y.len() > 0 {
+        let fold = if y.len() == 0 || (x.len() > 0 && rng.chance(0.5)) {
+            format!("fold along x={}", x.next().unwrap())
+        } else {
+            format!("fold along y={}", y.next().unwrap())
+        };
+        folds.push(fold);
+    }
+
+    format!("{}\n{}", lines(dots), lines(folds))
+}
+
+/// a polymer template with rules for every pair of `size` elements (at most 26).
+pub fn day14(rng: &mut Rng, size: usize) -> String {
+    let mut letters: Vec<char> = ('A'..='Z').collect();
+    rng.shuffle(&mut letters);
+    let elements = &letters[..size.clamp(2, 26)];
+
+    let template: String = (0..20).map(|_| *rng.choose(elements)).collect();
+
+    let rules = elements
+        .iter()
+        .cartesian_product(elements.iter())
+        .map(|(a, b)| format!("{}{} -> {}", a, b, rng.choose(elements)));
+
+    format!("{}\n\n{}", template, lines(rules))
+}
+
+/// a `size x size` map of risk levels.
+pub fn day15(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    grid(rng, size, size, |rng| {
+        char::from_digit(rng.range(1..=9) as u32, 10).unwrap()
+    })
+}
+
+fn bits_header(rng: &mut Rng, type_id: u64) -> Header {
+    Header {
+        version: rng.below(8) as u64,
+        type_id,
+    }
+}
+
+/// a random packet of at most `depth` nested operators.
+/// products only multiply literals, which keeps results well within `u64`.
+fn bits_packet(rng: &mut Rng, depth: usize) -> Packet {
+    let type_id = if depth == 0 {
+        4
+    } else {
+        *rng.choose(&[0, 1, 2, 3, 4, 5, 6, 7])
+    };
+
+    if type_id == 4 {
+        return Packet::Literal(Literal {
+            header: bits_header(rng, 4),
+            value: rng.below(1000) as u64,
+        });
+    }
+
+    let (count, child_depth) = match type_id {
+        1 => (rng.range(1..=3) as usize, 0),
+        5..=7 => (2, depth - 1),
+        _ => (rng.range(1..=3) as usize, depth - 1),
+    };
+
+    Packet::Operator(Operator {
+        header: bits_header(rng, type_id),
+        length_type: *rng.choose(&[LengthType::TotalLength, LengthType::PacketCount]),
+        children: (0..count).map(|_| bits_packet(rng, child_depth)).collect(),
+    })
+}
+
+/// a BITS transmission that sums `size` random expressions.
+/// an operator can count at most 2047 sub-packets, so larger sums are nested.
+pub fn day16(rng: &mut Rng, size: usize) -> String {
+    let mut packets: Vec<Packet> = (0..size.max(1)).map(|_| bits_packet(rng, 3)).collect();
+
+    loop {
+        packets = packets
+            .into_iter()
+            .chunks(2047)
+            .into_iter()
+            .map(|children| {
+                Packet::Operator(Operator {
+                    header: bits_header(rng, 0),
+                    length_type: LengthType::PacketCount,
+                    children: children.collect(),
+                })
+            })
+            .collect();
+
+        if packets.len() == 1 {
+            break;
+        }
+    }
+
+    // expressions are at most a few hundred bits long, so every operator can be encoded.
+    encode(&packets[0]).unwrap() + "\n"
+}
+
+/// a target area in front of and below the launcher. `size` is ignored.
+/// the velocity `(left, bottom)` always hits the target on the first step.
+pub fn day17(rng: &mut Rng, _size: usize) -> String {
+    let left = rng.range(10..=200);
+    let right = left + rng.range(5..=30);
+    let bottom = rng.range(-150..=-40);
+    let top = bottom + rng.range(5..=30);
+
+    format!(
+        "target area: x={}..{}, y={}..{}\n",
+        left, right, bottom, top
+    )
+}
+
+fn snail_number(rng: &mut Rng, depth: usize) -> String {
+    if depth == 0 || (depth < 4 && rng.chance(0.6)) {
+        format!(
+            "[{},{}]",
+            snail_number(rng, depth + 1),
+            snail_number(rng, depth + 1)
+        )
+    } else {
+        rng.below(10).to_string()
+    }
+}
+
+/// `size` reduced snailfish numbers.
+pub fn day18(rng: &mut Rng, size: usize) -> String {
+    lines((0..size.max(2)).map(|_| snail_number(rng, 0)))
+}
+
+type Vector = [i64; 3];
+
+/// A generated scanner report together with the answers it was built from.
+pub struct ScannerReports {
+    pub input: String,
+    /// number of distinct beacons.
+    pub beacons: usize,
+    /// largest manhattan distance between any two scanners.
+    pub max_distance: i64,
+}
+
+/// all 24 rotations as signed permutations with a determinant of `1`.
+fn rotations() -> Vec<[[i64; 3]; 3]> {
+    let mut rotations = Vec::new();
+
+    for axes in (0..3).permutations(3) {
+        for signs in 0..8 {
+            let mut m = [[0; 3]; 3];
+            for (row, axis) in axes.iter().enumerate() {
+                m[row][*axis] = if signs >> row & 1 == 1 { -1 } else { 1 };
+            }
+
+            let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+                - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+                + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+
+            if det == 1 {
+                rotations.push(m);
+            }
+        }
+    }
+
+    rotations
+}
+
+/// `size` scanners in a chain. every scanner shares 12 beacons with the previous one.
+/// reports are rotated and translated into the scanner's frame, scanner `0` is the reference frame.
+pub fn day19(rng: &mut Rng, size: usize) -> ScannerReports {
+    let rotations = rotations();
+    let mut all_beacons: HashSet<Vector> = HashSet::new();
+    let mut reports = Vec::new();
+    let mut previous_bridge: Vec<Vector> = Vec::new();
+
+    let mut scanners: Vec<Vector> = vec![[0, 0, 0]];
+    for i in 1..size.max(1) {
+        let last = scanners[i - 1];
+        scanners.push([0, 1, 2].map(|a| last[a] + rng.range(-1200..=1200)));
+    }
+
+    for (i, &position) in scanners.iter().enumerate() {
+        // scanners only see beacons in range, so the beacons shared with the next scanner lie in both ranges.
+        // further scanners never share beacons.
+        let bridge: Vec<Vector> = match scanners.get(i + 1) {
+            Some(next) => (0..12)
+                .map(|_| {
+                    [0, 1, 2].map(|a| {
+                        let from = position[a].max(next[a]) - 1000;
+                        let to = position[a].min(next[a]) + 1000;
+                        rng.range(from..=to)
+                    })
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let fresh =
+            (0..rng.range(2..=8)).map(|_| [0, 1, 2].map(|a| position[a] + rng.range(-1000..=1000)));
+
+        let mut beacons: Vec<Vector> = previous_bridge
+            .iter()
+            .chain(&bridge)
+            .copied()
+            .chain(fresh)
+            .collect();
+        rng.shuffle(&mut beacons);
+        all_beacons.extend(beacons.iter().copied());
+
+        // local coordinates are the inverse (=transposed) rotation applied to the offset.
+        let rotation = if i == 0 {
+            rotations[0]
+        } else {
+            *rng.choose(&rotations)
+        };
+        let local = beacons.iter().map(|b| {
+            let offset = [0, 1, 2].map(|a| b[a] - position[a]);
+            [0, 1, 2].map(|col| {
+                (0..3)
+                    .map(|row| rotation[row][col] * offset[row])
+                    .sum::<i64>()
+            })
+        });
+
+        let mut report = format!("--- scanner {} ---\n", i);
+        for [x, y, z] in local {
+            writeln!(report, "{},{},{}", x, y, z).unwrap();
+        }
+        reports.push(report);
+        previous_bridge = bridge;
+    }
+
+    let max_distance = scanners
+        .iter()
+        .tuple_combinations()
+        .map(|(a, b)| (0..3).map(|i| (a[i] - b[i]).abs()).sum())
+        .max()
+        .unwrap_or(0);
+
+    ScannerReports {
+        input: reports.join("\n"),
+        beacons: all_beacons.len(),
+        max_distance,
+    }
+}
+
+/// an enhancement cipher and a `size x size` image.
+/// if the cipher lights up dark pixels, it turns off lit ones so the infinite image stays finite.
+pub fn day20(rng: &mut Rng, size: usize) -> String {
+    let mut cipher: Vec<char> = (0..512).map(|_| *rng.choose(&['#', '.'])).collect();
+    if cipher[0] == '#' {
+        cipher[511] = '.';
+    }
+
+    let size = size.max(1);
+    let image = grid(rng, size, size, |rng| *rng.choose(&['#', '.']));
+
+    format!("{}\n\n{}", cipher.into_iter().collect::<String>(), image)
+}
+
+/// starting positions for two players. `size` is ignored.
+pub fn day21(rng: &mut Rng, _size: usize) -> String {
+    lines(
+        (1..=2).map(|player| format!("Player {} starting position: {}", player, rng.range(1..=10))),
+    )
+}
+
+/// `size` reboot steps. the first half covers the initialization area, the rest spans the whole reactor.
+pub fn day22(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+
+    lines((0..size).map(|i| {
+        let (bound, max_len) = if i < size.div_ceil(2) {
+            (50, 50)
+        } else {
+            (100_000, 50_000)
+        };
+
+        let ranges = ["x", "y", "z"]
+            .iter()
+            .map(|axis| {
+                let from = rng.range(-bound..=bound - 1);
+                let to = (from + rng.range(0..=max_len)).min(bound);
+                format!("{}={}..{}", axis, from, to)
+            })
+            .join(",");
+
+        let on = i == 0 || rng.chance(0.6);
+        format!("{} {}", if on { "on" } else { "off" }, ranges)
+    }))
+}
+
+/// a burrow with two amphipods of every kind in random rooms. `size` is ignored.
+pub fn day23(rng: &mut Rng, _size: usize) -> String {
+    let mut amphipods: Vec<char> = "AABBCCDD".chars().collect();
+    rng.shuffle(&mut amphipods);
+
+    let row = |r: &[char]| r.iter().join("#");
+
+    format!(
+        "#############\n#...........#\n###{}###\n  #{}#\n  #########\n",
+        row(&amphipods[..4]),
+        row(&amphipods[4..])
+    )
+}
+
+/// a MONAD program made up of 14 blocks that push or pop a digit on a base 26 stack.
+/// every pop is paired with a push so that at least one model number is valid. `size` is ignored.
+pub fn day24(rng: &mut Rng, _size: usize) -> String {
+    let mut blocks: Vec<(i64, i64, i64)> = Vec::new();
+    let mut stack: Vec<i64> = Vec::new();
+    let mut pushes = 7;
+
+    while blocks.len() < 14 {
+        if pushes > 0 && (stack.is_empty() || rng.chance(0.5)) {
+            let offset = rng.range(0..=16);
+            stack.push(offset);
+            blocks.push((1, rng.range(10..=16), offset));
+            pushes -= 1;
+        } else {
+            // the popped digit equals the pushed digit plus `offset + b`, which needs to be within `-8..=8`.
+            let offset = stack.pop().unwrap();
+            blocks.push((26, rng.range(-8..=8) - offset, rng.range(0..=16)));
+        }
+    }
+
+    lines(blocks.iter().map(|(a, b, c)| {
+        format!(
+            "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\nmul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\nmul y x\nadd z y",
+            a, b, c
+        )
+    }))
+}
+
+/// a `size x size` map of sea cucumber herds.
+pub fn day25(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    grid(rng, size, size, |rng| *rng.choose(&['>', 'v', '.', '.']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SolveError;
+    use crate::solutions::*;
+
+    macro_rules! assert_solves {
+        ($day:ident, $size:expr) => {
+            for seed in 0..3 {
+                let input = generate(stringify!($day)[3..].parse().unwrap(), $size, seed).unwrap();
+                if let Err(err) = $day::try_part_one(&input) {
+                    panic!("{} part one, seed {}: {}", stringify!($day), seed, err);
+                }
+                if let Err(err) = $day::try_part_two(&input) {
+                    panic!("{} part two, seed {}: {}", stringify!($day), seed, err);
+                }
+            }
+        };
+    }
+
+    #[test]
+    fn test_reproducible() {
+        assert_eq!(generate(4, 3, 7), generate(4, 3, 7));
+        assert_ne!(generate(4, 3, 7), generate(4, 3, 8));
+        assert_eq!(generate(26, 3, 7), None);
+    }
+
+    #[test]
+    fn test_generated_inputs() {
+        assert_solves!(day01, 200);
+        assert_solves!(day02, 200);
+        assert_solves!(day03, 200);
+        assert_solves!(day04, 20);
+        assert_solves!(day05, 100);
+        assert_solves!(day06, 100);
+        assert_solves!(day07, 100);
+        assert_solves!(day08, 50);
+        assert_solves!(day09, 30);
+        assert_solves!(day10, 50);
+        assert_solves!(day12, 5);
+        assert_solves!(day13, 200);
+        assert_solves!(day14, 10);
+        assert_solves!(day15, 20);
+        assert_solves!(day16, 50);
+        assert_solves!(day17, 0);
+        assert_solves!(day18, 20);
+        assert_solves!(day20, 20);
+        assert_solves!(day21, 0);
+        assert_solves!(day22, 20);
+        assert_solves!(day23, 0);
+        assert_solves!(day24, 0);
+        assert_solves!(day25, 30);
+    }
+
+    #[test]
+    fn test_generated_octopuses() {
+        for seed in 0..3 {
+            let input = generate(11, 0, seed).unwrap();
+            assert!(day11::try_part_one(&input).is_ok());
+            assert!(matches!(
+                day11::try_part_two(&input),
+                Ok(_) | Err(SolveError::NoSolution(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_generated_scanners() {
+        for seed in 0..3 {
+            let reports = day19(&mut Rng::new(seed), 5);
+            assert_eq!(day19::try_part_one(&reports.input), Ok(reports.beacons));
+            assert_eq!(
+                day19::try_part_two(&reports.input),
+                Ok(reports.max_distance as i32)
+            );
+        }
+    }
+}
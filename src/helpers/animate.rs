@@ -0,0 +1,80 @@
+use super::trace::Observer;
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::ops::ControlFlow;
+use std::thread;
+use std::time::Duration;
+
+/// move the cursor home and clear the screen.
+static ANSI_CLEAR: &str = "\x1b[H\x1b[2J";
+
+/// Plays a simulation in the terminal by redrawing every observed state as a frame.
+pub struct Player {
+    delay: Duration,
+    skip: usize,
+    frames: usize,
+    /// the latest frame that was skipped, drawn by `finish` so the animation always ends on the final state.
+    pending: Option<String>,
+}
+
+impl Player {
+    /// Draw `fps` frames per second, showing every `skip`th step. Both need to be positive.
+    pub fn new(fps: f64, skip: usize) -> Self {
+        assert!(fps > 0.0 && skip > 0, "fps and skip need to be positive");
+
+        Player {
+            delay: Duration::from_secs_f64(1.0 / fps),
+            skip,
+            frames: 0,
+            pending: None,
+        }
+    }
+
+    fn draw(&self, frame: &str) {
+        let mut out = io::stdout().lock();
+        write!(out, "{}{}\nstep {}\n", ANSI_CLEAR, frame, self.frames).unwrap();
+        out.flush().unwrap();
+        thread::sleep(self.delay);
+    }
+
+    /// Draw the last state if it was skipped.
+    pub fn finish(&mut self) {
+        if let Some(frame) = self.pending.take() {
+            self.draw(&frame);
+        }
+    }
+}
+
+impl<S: Display + ?Sized> Observer<S> for Player {
+    fn observe(&mut self, state: &S) -> ControlFlow<()> {
+        self.frames += 1;
+
+        if (self.frames - 1).is_multiple_of(self.skip) {
+            self.pending = None;
+            self.draw(&state.to_string());
+        } else {
+            self.pending = Some(state.to_string());
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip() {
+        let mut player = Player::new(1e6, 3);
+
+        for step in 1..=5 {
+            assert_eq!(player.observe(&step), ControlFlow::Continue(()));
+            assert_eq!(player.pending.is_none(), step % 3 == 1);
+        }
+
+        assert_eq!(player.pending.as_deref(), Some("5"));
+        player.finish();
+        assert_eq!(player.pending, None);
+    }
+}
@@ -1,4 +1,6 @@
+use super::render::Render;
 use std::fmt::Debug;
+use std::ops::{Index, IndexMut};
 
 /// A point describes a location `x, y` in a grid with two axis.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -61,3 +63,462 @@ impl Point {
         neighbors
     }
 }
+
+/// An axis of a grid. Used to describe folds.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+/// Describes which cells are adjacent when looking for connected regions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Horizontal and vertical neighbors.
+    Four,
+    /// Horizontal, vertical and diagonal neighbors.
+    Eight,
+}
+
+/// A connected region of cells in a grid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    /// Number of cells in the region.
+    pub size: usize,
+    /// Top-left corner of the region's bounding box.
+    pub min: Point,
+    /// Bottom-right corner of the region's bounding box.
+    pub max: Point,
+}
+
+/// Result of labelling the connected regions of a grid.
+#[derive(Clone, Debug)]
+pub struct Regions {
+    /// For every cell, the index of the region in `regions` it belongs to, if any.
+    pub labels: Grid<Option<usize>>,
+    pub regions: Vec<Region>,
+}
+
+/// A dense grid of `width * height` cells, stored row by row.
+/// Cells are addressed with `Point`s, `Point::to_id` gives the index of a cell in storage.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from a list of rows. All rows need to have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "rows of a grid need to have the same length"
+        );
+
+        Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Create a grid by calling `f` for every point, row by row.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: (0..width * height)
+                .map(|id| f(Point::from_id(id, width)))
+                .collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        if self.contains(p) {
+            Some(&self.cells[p.to_id(self.width)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.cells[p.to_id(self.width)])
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(&self, Point(x, y): Point) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Iterate all cells, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Iterate all points, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |id| Point::from_id(id, width))
+    }
+
+    /// Iterate all rows, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics for a chunk size of zero, an empty grid has no rows to yield.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Get all neighbors for a point in this grid. Points outside the grid, e.g. in an empty grid, have none.
+    pub fn neighbors(&self, p: Point, include_diagonals: bool) -> Vec<Point> {
+        if !self.contains(p) {
+            return Vec::new();
+        }
+
+        p.neighbors(self.width - 1, self.height - 1, include_diagonals)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Label all connected regions made up of cells that match `predicate`.
+    /// Regions are numbered in the order their first cell appears, row by row.
+    /// Uses an explicit stack instead of recursion so large regions can not overflow the call stack.
+    pub fn label_regions(
+        &self,
+        connectivity: Connectivity,
+        predicate: impl Fn(&T) -> bool,
+    ) -> Regions {
+        let include_diagonals = connectivity == Connectivity::Eight;
+        let mut labels = Grid::filled(self.width, self.height, None);
+        let mut regions = Vec::new();
+        let mut stack = Vec::new();
+
+        for start in self.points() {
+            if labels[start].is_some() || !predicate(&self[start]) {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut region = Region {
+                size: 0,
+                min: start,
+                max: start,
+            };
+
+            labels[start] = Some(label);
+            stack.push(start);
+
+            while let Some(p) = stack.pop() {
+                region.size += 1;
+                region.min = Point(region.min.0.min(p.0), region.min.1.min(p.1));
+                region.max = Point(region.max.0.max(p.0), region.max.1.max(p.1));
+
+                for n in self.neighbors(p, include_diagonals) {
+                    if labels[n].is_none() && predicate(&self[n]) {
+                        labels[n] = Some(label);
+                        stack.push(n);
+                    }
+                }
+            }
+
+            regions.push(region);
+        }
+
+        Regions { labels, regions }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Create a grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Rotate the grid clockwise by `quarter_turns` steps of 90°.
+    /// Negative values rotate counter-clockwise.
+    pub fn rotate(&self, quarter_turns: i32) -> Self {
+        match quarter_turns.rem_euclid(4) {
+            0 => self.clone(),
+            1 => Grid::from_fn(self.height, self.width, |Point(x, y)| {
+                self[Point(y, self.height - x - 1)].clone()
+            }),
+            2 => Grid::from_fn(self.width, self.height, |Point(x, y)| {
+                self[Point(self.width - x - 1, self.height - y - 1)].clone()
+            }),
+            _ => Grid::from_fn(self.height, self.width, |Point(x, y)| {
+                self[Point(self.width - y - 1, x)].clone()
+            }),
+        }
+    }
+
+    /// Mirror the grid left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        Grid::from_fn(self.width, self.height, |Point(x, y)| {
+            self[Point(self.width - x - 1, y)].clone()
+        })
+    }
+
+    /// Mirror the grid top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        Grid::from_fn(self.width, self.height, |Point(x, y)| {
+            self[Point(x, self.height - y - 1)].clone()
+        })
+    }
+
+    /// Swap rows and columns of the grid.
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |Point(x, y)| {
+            self[Point(y, x)].clone()
+        })
+    }
+
+    /// Cut out the bounding box spanned by `min` and `max` (inclusive).
+    pub fn crop(&self, min: Point, max: Point) -> Self {
+        assert!(
+            min.0 <= max.0 && min.1 <= max.1 && self.contains(max),
+            "crop bounds need to be inside the grid"
+        );
+
+        Grid::from_fn(max.0 - min.0 + 1, max.1 - min.1 + 1, |Point(x, y)| {
+            self[Point(min.0 + x, min.1 + y)].clone()
+        })
+    }
+
+    /// Repeat the grid `n` times horizontally and `m` times vertically.
+    /// `map` receives every original cell and the position of its tile, `(tile_x, tile_y)`.
+    pub fn tile(&self, n: usize, m: usize, map: impl Fn(&T, usize, usize) -> T) -> Self {
+        Grid::from_fn(self.width * n, self.height * m, |Point(x, y)| {
+            let cell = &self[Point(x % self.width, y % self.height)];
+            map(cell, x / self.width, y / self.height)
+        })
+    }
+
+    /// Fold the grid along the line `axis = at`. The line itself is removed.
+    /// The far half is mirrored onto the near half and cells that end up on top of each other are combined with `merge`.
+    /// If the far half is larger, the result grows so that no cells are lost.
+    pub fn fold(&self, axis: Axis, at: usize, merge: impl Fn(&T, &T) -> T) -> Self {
+        let len = match axis {
+            Axis::X => self.width,
+            Axis::Y => self.height,
+        };
+
+        assert!(at < len, "fold line needs to be inside the grid");
+
+        let far_len = len - at - 1;
+        let folded_len = at.max(far_len);
+
+        // positions along the folding axis are counted towards the fold line, which becomes the far edge.
+        let near = |i: usize| i.checked_sub(folded_len - at).filter(|i| *i < at);
+        let far = |i: usize| Some(at + folded_len - i).filter(|i| *i < len && *i > at);
+
+        let (width, height) = match axis {
+            Axis::X => (folded_len, self.height),
+            Axis::Y => (self.width, folded_len),
+        };
+
+        Grid::from_fn(width, height, |Point(x, y)| {
+            let i = match axis {
+                Axis::X => x,
+                Axis::Y => y,
+            };
+            let source = |i: usize| match axis {
+                Axis::X => Point(i, y),
+                Axis::Y => Point(x, i),
+            };
+
+            match (near(i), far(i)) {
+                (Some(a), Some(b)) => merge(&self[source(a)], &self[source(b)]),
+                (Some(a), None) => self[source(a)].clone(),
+                (None, Some(b)) => self[source(b)].clone(),
+                (None, None) => unreachable!(),
+            }
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p).expect("point is outside of grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p).expect("point is outside of grid")
+    }
+}
+
+impl<T> Render for Grid<T> {
+    type Cell = T;
+
+    fn rows(&self) -> Vec<&[T]> {
+        Grid::rows(self).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<u32> {
+        Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]])
+    }
+
+    #[test]
+    fn test_rotate() {
+        assert_eq!(
+            sample().rotate(1),
+            Grid::from_rows(vec![vec![4, 1], vec![5, 2], vec![6, 3]])
+        );
+        assert_eq!(
+            sample().rotate(2),
+            Grid::from_rows(vec![vec![6, 5, 4], vec![3, 2, 1]])
+        );
+        assert_eq!(sample().rotate(-1), sample().rotate(3));
+        assert_eq!(sample().rotate(4), sample());
+    }
+
+    #[test]
+    fn test_flip_and_transpose() {
+        assert_eq!(
+            sample().flip_horizontal(),
+            Grid::from_rows(vec![vec![3, 2, 1], vec![6, 5, 4]])
+        );
+        assert_eq!(
+            sample().flip_vertical(),
+            Grid::from_rows(vec![vec![4, 5, 6], vec![1, 2, 3]])
+        );
+        assert_eq!(
+            sample().transpose(),
+            Grid::from_rows(vec![vec![1, 4], vec![2, 5], vec![3, 6]])
+        );
+    }
+
+    #[test]
+    fn test_crop_and_tile() {
+        assert_eq!(
+            sample().crop(Point(1, 0), Point(2, 1)),
+            Grid::from_rows(vec![vec![2, 3], vec![5, 6]])
+        );
+        assert_eq!(
+            Grid::from_rows(vec![vec![1]]).tile(3, 2, |v, x, y| v + (x + y) as u32),
+            Grid::from_rows(vec![vec![1, 2, 3], vec![2, 3, 4]])
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let mut neighbors = sample().neighbors(Point(2, 0), true);
+        neighbors.sort();
+        assert_eq!(neighbors, vec![Point(1, 0), Point(1, 1), Point(2, 1)]);
+
+        assert!(sample().neighbors(Point(3, 0), false).is_empty());
+        assert!(Grid::<u32>::filled(0, 0, 0)
+            .neighbors(Point(0, 0), true)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_label_regions() {
+        let grid = Grid::from_rows(vec![vec![1, 1, 0, 0], vec![0, 0, 1, 0], vec![1, 0, 1, 1]]);
+
+        let four = grid.label_regions(Connectivity::Four, |x| *x == 1);
+        assert_eq!(
+            four.regions.iter().map(|r| r.size).collect::<Vec<_>>(),
+            vec![2, 3, 1]
+        );
+        assert_eq!(four.labels[Point(3, 2)], Some(1));
+        assert_eq!(four.labels[Point(0, 1)], None);
+        assert_eq!(
+            four.regions[1],
+            Region {
+                size: 3,
+                min: Point(2, 1),
+                max: Point(3, 2)
+            }
+        );
+
+        let eight = grid.label_regions(Connectivity::Eight, |x| *x == 1);
+        assert_eq!(
+            eight.regions.iter().map(|r| r.size).collect::<Vec<_>>(),
+            vec![5, 1]
+        );
+    }
+
+    #[test]
+    fn test_fold() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 0, 4, 8]]);
+        assert_eq!(
+            grid.fold(Axis::X, 2, |a, b| a + b),
+            Grid::from_rows(vec![vec![9, 6]])
+        );
+        assert_eq!(
+            grid.fold(Axis::X, 1, |a, b| a + b),
+            Grid::from_rows(vec![vec![8, 4, 1]])
+        );
+        assert_eq!(
+            sample().fold(Axis::Y, 1, |a, b| a + b),
+            Grid::from_rows(vec![vec![1, 2, 3]])
+        );
+    }
+
+    #[test]
+    fn test_point_properties() {
+        use crate::helpers::prop::check;
+        use itertools::Itertools;
+
+        check::<(u8, u8, usize)>(|&(width, height, id)| {
+            let (width, height) = (width as usize % 16 + 1, height as usize % 16 + 1);
+            let p = Point::from_id(id % (width * height), width);
+            Point::from_id(p.to_id(width), width) == p
+        });
+
+        check::<(u8, u8, bool)>(|&(width, height, include_diagonals)| {
+            let grid = Grid::filled(width as usize % 8 + 1, height as usize % 8 + 1, ());
+            grid.points().all(|p| {
+                let neighbors = grid.neighbors(p, include_diagonals);
+                neighbors.iter().all(|&q| {
+                    grid.contains(q) && q != p && grid.neighbors(q, include_diagonals).contains(&p)
+                }) && neighbors.iter().all_unique()
+            })
+        });
+    }
+
+    #[test]
+    fn test_transform_properties() {
+        use crate::helpers::prop::check;
+
+        check::<(Vec<u8>, u8)>(|(cells, width)| {
+            let width = *width as usize % 4 + 1;
+            let rows: Vec<Vec<u8>> = cells.chunks_exact(width).map(|c| c.to_vec()).collect();
+            if rows.is_empty() {
+                return true;
+            }
+
+            let grid = Grid::from_rows(rows);
+            grid.rotate(4) == grid
+                && grid.rotate(1).rotate(-1) == grid
+                && grid.transpose().transpose() == grid
+                && grid.flip_horizontal().flip_horizontal() == grid
+                && grid.rotate(2) == grid.flip_horizontal().flip_vertical()
+        });
+    }
+}
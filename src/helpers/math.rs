@@ -1,14 +1,94 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// common interface of the primitive integer types used by the generic helpers in this module.
+pub trait Integer:
+    Copy
+    + Debug
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_rem(self, other: Self) -> Option<Self>;
+    fn to_f64(self) -> f64;
+}
+
+/// marker for integer types that can represent negative numbers.
+pub trait Signed: Integer {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn checked_rem(self, other: Self) -> Option<Self> {
+                    <$t>::checked_rem(self, other)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+/// `None` if `a` is the minimum of a signed type, whose absolute value does not fit.
+fn checked_abs<T: Integer>(a: T) -> Option<T> {
+    if a < T::ZERO {
+        T::ZERO.checked_sub(a)
+    } else {
+        Some(a)
+    }
+}
+
 /// get a vector's median value.
 /// the median is the value separating the higher half from the lower half of a data sample.
 /// [Wikipedia](https://en.wikipedia.org/wiki/Median)
-pub fn median(vec: &mut Vec<u64>) -> u64 {
+pub fn median(vec: &mut [u64]) -> u64 {
     let len = vec.len();
     let mid = len / 2;
 
     vec.sort_unstable();
 
-    if len % 2 == 0 {
-        (vec[mid - 1] + vec[mid]) / 2
+    if len.is_multiple_of(2) {
+        // the slice is sorted, so adding half the gap rounds down like `(a + b) / 2` without overflowing.
+        vec[mid - 1] + (vec[mid] - vec[mid - 1]) / 2
     } else {
         vec[mid]
     }
@@ -20,6 +100,253 @@ pub fn nth_triangular(a: u64) -> u64 {
     a * (a + 1) / 2
 }
 
+/// like `nth_triangular`, but `None` if the result does not fit into a `u64`.
+pub fn checked_nth_triangular(a: u64) -> Option<u64> {
+    // halve the even factor first so the product is the only step that can overflow.
+    if a.is_multiple_of(2) {
+        (a / 2).checked_mul(a + 1)
+    } else {
+        a.checked_mul(a / 2 + 1)
+    }
+}
+
+/// get the arithmetic mean of a slice, `None` if it is empty.
+/// [Wikipedia](https://en.wikipedia.org/wiki/Arithmetic_mean)
+pub fn mean<T: Integer>(values: &[T]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        let sum: f64 = values.iter().map(|x| x.to_f64()).sum();
+        Some(sum / values.len() as f64)
+    }
+}
+
+/// get the most frequent value of a slice, `None` if it is empty.
+/// if several values are equally frequent, the smallest one is returned.
+/// [Wikipedia](https://en.wikipedia.org/wiki/Mode_(statistics))
+pub fn mode<T: Integer>(values: &[T]) -> Option<T> {
+    let mut counts: HashMap<T, usize> = HashMap::new();
+
+    for x in values {
+        *counts.entry(*x).or_default() += 1;
+    }
+
+    counts
+        .into_iter()
+        .max_by(|(a, count_a), (b, count_b)| count_a.cmp(count_b).then(b.cmp(a)))
+        .map(|(x, _)| x)
+}
+
+/// get the `p`th percentile (`0..=100`) of a slice with the nearest-rank method, `None` if it is empty.
+/// [Wikipedia](https://en.wikipedia.org/wiki/Percentile#The_nearest-rank_method)
+pub fn percentile<T: Integer>(values: &mut [T], p: f64) -> Option<T> {
+    assert!(
+        (0.0..=100.0).contains(&p),
+        "percentile needs to be in 0..=100"
+    );
+
+    if values.is_empty() {
+        return None;
+    }
+
+    values.sort_unstable();
+    let rank = (p / 100.0 * values.len() as f64).ceil() as usize;
+    Some(values[rank.saturating_sub(1)])
+}
+
+/// get the greatest common divisor of two integers. the result is never negative,
+/// so it is `None` if it does not fit into `T` (e.g. `checked_gcd(i32::MIN, 0)`).
+/// [Wikipedia](https://en.wikipedia.org/wiki/Euclidean_algorithm)
+pub fn checked_gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a, b);
+
+    while b != T::ZERO {
+        // only `MIN % -1` overflows, and its remainder is zero.
+        let r = a.checked_rem(b).unwrap_or(T::ZERO);
+        a = b;
+        b = r;
+    }
+
+    checked_abs(a)
+}
+
+/// get the greatest common divisor of two integers.
+/// panics on overflow, see `checked_gcd`.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    checked_gcd(a, b).expect("gcd overflowed")
+}
+
+/// get the least common multiple of two integers, `None` if it overflows.
+/// [Wikipedia](https://en.wikipedia.org/wiki/Least_common_multiple)
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        Some(T::ZERO)
+    } else {
+        checked_abs(a / checked_gcd(a, b)?)?.checked_mul(checked_abs(b)?)
+    }
+}
+
+/// get the least common multiple of two integers.
+/// panics on overflow, see `checked_lcm`.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm overflowed")
+}
+
+/// extended euclidean algorithm: get `(gcd, x, y)` so that `a * x + b * y == gcd`.
+/// [Wikipedia](https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm)
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::ZERO {
+        (T::ZERO - old_r, T::ZERO - old_x, T::ZERO - old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// get the modular multiplicative inverse of `a` modulo `m`, `None` if `a` and `m` are not coprime.
+/// [Wikipedia](https://en.wikipedia.org/wiki/Modular_multiplicative_inverse)
+pub fn mod_inverse<T: Signed>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(a, m);
+
+    if g == T::ONE {
+        // `x % m` is in `-m..m`, adding `m` only to negative remainders keeps large moduli from overflowing.
+        let r = x % m;
+        Some(if r < T::ZERO { r + m } else { r })
+    } else {
+        None
+    }
+}
+
+/// calculate `a + b mod modulus` for non-negative `a` and `b` without overflowing.
+pub fn add_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    let (a, b) = (a % modulus, b % modulus);
+
+    // rearranged from `a + b >= modulus` so that it can not overflow.
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+/// calculate `a * b mod modulus` for non-negative `a` and `b` without overflowing.
+/// falls back to double-and-add if the product does not fit into `T`.
+/// [Wikipedia](https://en.wikipedia.org/wiki/Modular_arithmetic)
+pub fn mul_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    assert!(modulus > T::ZERO, "modulus needs to be positive");
+
+    let (mut a, mut b) = (a % modulus, b % modulus);
+
+    if let Some(product) = a.checked_mul(b) {
+        return product % modulus;
+    }
+
+    let two = T::ONE + T::ONE;
+    let mut result = T::ZERO;
+
+    while b > T::ZERO {
+        if b % two == T::ONE {
+            result = add_mod(result, a, modulus);
+        }
+        a = add_mod(a, a, modulus);
+        b = b / two;
+    }
+
+    result
+}
+
+/// calculate `base^exp mod modulus` by repeated squaring. the result always fits into `T`, and so does every
+/// intermediate product thanks to `mul_mod`.
+/// [Wikipedia](https://en.wikipedia.org/wiki/Modular_exponentiation)
+pub fn mod_pow<T: Integer>(base: T, exp: u64, modulus: T) -> T {
+    assert!(modulus > T::ZERO, "modulus needs to be positive");
+
+    let mut result = T::ONE % modulus;
+    let mut base = base % modulus;
+    if base < T::ZERO {
+        base = base + modulus;
+    }
+    let mut exp = exp;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// solve a system of congruences `x = r (mod m)` given as `(r, m)` pairs.
+/// returns `(x, lcm of all m)` with the smallest non-negative `x`.
+/// moduli do not need to be coprime. `None` if the congruences contradict each other or the result overflows.
+/// [Wikipedia](https://en.wikipedia.org/wiki/Chinese_remainder_theorem)
+pub fn chinese_remainder<T: Signed>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    congruences
+        .iter()
+        .try_fold((T::ZERO, T::ONE), |(r1, m1), &(r2, m2)| {
+            assert!(m2 > T::ZERO, "moduli need to be positive");
+
+            let (g, p, _) = extended_gcd(m1, m2);
+            let diff = r2.checked_sub(r1)?;
+
+            if diff % g != T::ZERO {
+                return None;
+            }
+
+            let m = checked_lcm(m1, m2)?;
+            // x = r1 + m1 * ((r2 - r1) / g * p mod (m2 / g))
+            let step = m2 / g;
+            let k = ((diff / g % step).checked_mul(p % step)? % step + step) % step;
+            let x = r1.checked_add(m1.checked_mul(k)?)? % m;
+
+            Some((x, m))
+        })
+}
+
+/// get the integer square root `floor(sqrt(n))`, `None` for negative numbers.
+/// [Wikipedia](https://en.wikipedia.org/wiki/Integer_square_root)
+pub fn checked_isqrt<T: Integer>(n: T) -> Option<T> {
+    if n < T::ZERO {
+        return None;
+    }
+
+    if n < T::ONE + T::ONE {
+        return Some(n);
+    }
+
+    // newton's method, starting from an estimate that is guaranteed to be too large.
+    // `x` never drops below the root, so `n / x` stays close to it and `x + n / x` can not overflow.
+    let two = T::ONE + T::ONE;
+    let mut x = n / two + T::ONE;
+    let mut y = (x + n / x) / two;
+
+    while y < x {
+        x = y;
+        y = (x + n / x) / two;
+    }
+
+    Some(x)
+}
+
+/// get the integer square root `floor(sqrt(n))`.
+/// panics for negative numbers, see `checked_isqrt`.
+pub fn isqrt<T: Integer>(n: T) -> T {
+    checked_isqrt(n).expect("isqrt of a negative number")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -28,10 +355,122 @@ mod tests {
     fn test_median() {
         assert_eq!(median(&mut [1, 4, 7].to_vec()), 4);
         assert_eq!(median(&mut [3, 10, 36, 255, 79, 24, 5, 8].to_vec()), 17);
+        assert_eq!(median(&mut [u64::MAX, u64::MAX - 2].to_vec()), u64::MAX - 1);
+    }
+
+    #[test]
+    fn test_median_properties() {
+        use crate::helpers::prop::check;
+
+        check::<Vec<u64>>(|values| {
+            if values.is_empty() {
+                return true;
+            }
+
+            // sort-based definition: the mean of the middle value(s), computed without overflow.
+            let mut sorted = values.clone();
+            sorted.sort();
+            let mid = sorted.len() / 2;
+            let expected = if sorted.len() % 2 == 0 {
+                ((sorted[mid - 1] as u128 + sorted[mid] as u128) / 2) as u64
+            } else {
+                sorted[mid]
+            };
+
+            let mut shuffled = values.clone();
+            shuffled.reverse();
+            median(&mut values.clone()) == expected && median(&mut shuffled) == expected
+        });
     }
 
     #[test]
     fn test_nth_triangular() {
         assert_eq!(nth_triangular(7), 28);
+        assert_eq!(checked_nth_triangular(7), Some(28));
+        assert_eq!(checked_nth_triangular(1 << 33), None);
+        assert_eq!(checked_nth_triangular(u64::MAX), None);
+    }
+
+    #[test]
+    fn test_statistics() {
+        assert_eq!(mean(&[1, 2, 3, 4u8]), Some(2.5));
+        assert_eq!(mean::<i32>(&[]), None);
+        assert_eq!(mode(&[3, 1, 3, 1, 2i64]), Some(1));
+        assert_eq!(percentile(&mut [15, 20, 35, 40, 50u32], 40.0), Some(20));
+        assert_eq!(percentile(&mut [15, 20, 35, 40, 50u32], 100.0), Some(50));
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(48u32, 18), 6);
+        assert_eq!(gcd(-48i32, 18), 6);
+        assert_eq!(lcm(4u64, 6), 12);
+        assert_eq!(checked_lcm(u8::MAX, 254), None);
+        assert_eq!(checked_gcd(i32::MIN, 0), None);
+        assert_eq!(checked_gcd(i32::MIN, i32::MIN), None);
+        assert_eq!(checked_gcd(i32::MIN, -1), Some(1));
+        assert_eq!(checked_gcd(i32::MIN, 6), Some(2));
+        assert_eq!(checked_lcm(i32::MIN, 1), None);
+        assert_eq!(checked_lcm(i32::MIN, 2), None);
+        assert_eq!(checked_lcm(1, i32::MIN + 1), Some(i32::MAX));
+        assert_eq!(extended_gcd(240i64, 46), (2, -9, 47));
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(mod_inverse(3i32, 11), Some(4));
+        assert_eq!(mod_inverse(2i32, 4), None);
+        assert_eq!(mod_pow(4u64, 13, 497), 445);
+        assert_eq!(add_mod(250u8, 250, 251), 249);
+        assert_eq!(mul_mod(250u8, 250, 251), 1);
+        assert_eq!(mul_mod(u64::MAX - 1, u64::MAX - 1, u64::MAX), 1);
+        assert_eq!(mod_pow(200u8, 2, 201), 1);
+        assert_eq!(mod_pow(u64::MAX - 1, 3, u64::MAX), u64::MAX - 1);
+        assert_eq!(mod_pow(-2i64, 3, 5), 2);
+        assert_eq!(mod_inverse(i64::MAX - 1, i64::MAX), Some(i64::MAX - 1));
+        assert_eq!(
+            chinese_remainder(&[(0i64, 3), (3, 4), (4, 5)]),
+            Some((39, 60))
+        );
+        assert_eq!(chinese_remainder(&[(1i64, 6), (3, 4)]), Some((7, 12)));
+        assert_eq!(chinese_remainder(&[(1i64, 6), (2, 4)]), None);
+    }
+
+    #[test]
+    fn test_modular_exhaustive() {
+        // every modulus of the small types, checked against the same calculation in a wider type.
+        for m in 1..=u8::MAX {
+            for a in 0..=u8::MAX {
+                let expected = (1..=5).fold(1 % m as u32, |acc, _| acc * a as u32 % m as u32);
+                assert_eq!(mod_pow(a, 5, m) as u32, expected, "{}^5 mod {}", a, m);
+            }
+        }
+
+        for m in 2..=i8::MAX {
+            for a in 0..m {
+                let expected = (0..m).find(|&b| a as i32 * b as i32 % m as i32 == 1);
+                assert_eq!(mod_inverse(a, m), expected, "inverse of {} mod {}", a, m);
+            }
+        }
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0u32), 0);
+        assert_eq!(isqrt(15u32), 3);
+        assert_eq!(isqrt(16u32), 4);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(checked_isqrt(-1i32), None);
+        assert_eq!(isqrt(i64::MAX), 3037000499);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(isqrt(i128::MAX), 13043817825332782212);
+
+        for n in 0..=u8::MAX {
+            let root = isqrt(n) as u32;
+            assert!(root * root <= n as u32 && (root + 1) * (root + 1) > n as u32);
+        }
+        for n in 0..=i8::MAX {
+            assert_eq!(isqrt(n) as u8, isqrt(n as u8));
+        }
     }
 }
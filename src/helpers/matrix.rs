@@ -0,0 +1,380 @@
+use super::math::{add_mod, mul_mod, Integer};
+use std::ops::{Index, IndexMut, Mul};
+
+/// A matrix with `N` rows and `M` columns. Dimensions are checked at compile time.
+/// Values are stored on the heap row by row, so large matrices do not exhaust the stack.
+/// Square matrices can be raised to a power by repeated squaring. This allows to evaluate
+/// linear recurrences (e.g. population growth) for huge step counts in logarithmic time.
+/// [Wikipedia](https://en.wikipedia.org/wiki/Matrix_exponentiation)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix<const N: usize, const M: usize, T = u64> {
+    inner: DynMatrix<T>,
+}
+
+impl<const N: usize, const M: usize, T: Integer> Matrix<N, M, T> {
+    pub fn zero() -> Self {
+        Matrix {
+            inner: DynMatrix::zero(N, M),
+        }
+    }
+
+    pub fn from_rows(rows: [[T; M]; N]) -> Self {
+        Matrix {
+            inner: DynMatrix::from_rows(rows.iter().map(|row| row.to_vec()).collect()),
+        }
+    }
+
+    /// Multiply with another matrix, `None` if any value overflows.
+    pub fn checked_mul<const P: usize>(&self, other: &Matrix<M, P, T>) -> Option<Matrix<N, P, T>> {
+        Some(Matrix {
+            inner: self.inner.checked_mul(&other.inner)?,
+        })
+    }
+
+    /// Multiply with another matrix, reducing every value modulo `modulus`.
+    pub fn mul_mod<const P: usize>(&self, other: &Matrix<M, P, T>, modulus: T) -> Matrix<N, P, T> {
+        Matrix {
+            inner: self.inner.mul_mod(&other.inner, modulus),
+        }
+    }
+
+    /// Reduce every value modulo `modulus`.
+    pub fn reduce(&self, modulus: T) -> Self {
+        Matrix {
+            inner: self.inner.reduce(modulus),
+        }
+    }
+
+    /// Multiply with a column vector, `None` if any value overflows.
+    pub fn checked_apply(&self, vector: &[T; M]) -> Option<[T; N]> {
+        let product = self.inner.checked_apply(vector)?;
+        Some(std::array::from_fn(|i| product[i]))
+    }
+
+    /// Multiply with a column vector.
+    /// Panics on overflow, see `checked_apply`.
+    pub fn apply(&self, vector: &[T; M]) -> [T; N] {
+        self.checked_apply(vector)
+            .expect("matrix product overflowed")
+    }
+
+    /// Multiply with a column vector, reducing every value modulo `modulus`.
+    pub fn apply_mod(&self, vector: &[T; M], modulus: T) -> [T; N] {
+        let product = self.inner.apply_mod(vector, modulus);
+        std::array::from_fn(|i| product[i])
+    }
+}
+
+impl<const N: usize, T: Integer> Matrix<N, N, T> {
+    pub fn identity() -> Self {
+        Matrix {
+            inner: DynMatrix::identity(N),
+        }
+    }
+
+    /// Raise the matrix to the power of `exp`, `None` if any value overflows.
+    pub fn checked_pow(&self, exp: u64) -> Option<Self> {
+        Some(Matrix {
+            inner: self.inner.checked_pow(exp)?,
+        })
+    }
+
+    /// Raise the matrix to the power of `exp`.
+    /// Panics on overflow, see `checked_pow` and `pow_mod`.
+    pub fn pow(&self, exp: u64) -> Self {
+        self.checked_pow(exp).expect("matrix power overflowed")
+    }
+
+    /// Raise the matrix to the power of `exp`, reducing every value modulo `modulus`.
+    pub fn pow_mod(&self, exp: u64, modulus: T) -> Self {
+        Matrix {
+            inner: self.inner.pow_mod(exp, modulus),
+        }
+    }
+}
+
+impl<const N: usize, const M: usize, const P: usize, T: Integer> Mul<&Matrix<M, P, T>>
+    for &Matrix<N, M, T>
+{
+    type Output = Matrix<N, P, T>;
+
+    /// Panics on overflow, see `Matrix::checked_mul`.
+    fn mul(self, other: &Matrix<M, P, T>) -> Matrix<N, P, T> {
+        self.checked_mul(other).expect("matrix product overflowed")
+    }
+}
+
+/// Values are indexed by `(row, column)`.
+impl<const N: usize, const M: usize, T> Index<(usize, usize)> for Matrix<N, M, T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &T {
+        &self.inner[index]
+    }
+}
+
+impl<const N: usize, const M: usize, T> IndexMut<(usize, usize)> for Matrix<N, M, T> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut T {
+        &mut self.inner[index]
+    }
+}
+
+/// Like `Matrix`, but with `rows` rows and `columns` columns chosen at runtime, for matrices that follow the input.
+/// `Matrix` is a thin wrapper around it, whose dimensions always match.
+/// Operations panic if the dimensions of their operands do not match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DynMatrix<T = u64> {
+    rows: usize,
+    columns: usize,
+    values: Vec<T>,
+}
+
+impl<T: Integer> DynMatrix<T> {
+    pub fn zero(rows: usize, columns: usize) -> Self {
+        DynMatrix {
+            rows,
+            columns,
+            values: vec![T::ZERO; rows * columns],
+        }
+    }
+
+    /// Panics if the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let columns = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == columns),
+            "rows need to have the same length"
+        );
+
+        DynMatrix {
+            rows: rows.len(),
+            columns,
+            values: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Multiply with another matrix, `None` if any value overflows.
+    /// Panics if the number of columns does not match the number of rows of `other`.
+    pub fn checked_mul(&self, other: &DynMatrix<T>) -> Option<DynMatrix<T>> {
+        assert_eq!(self.columns, other.rows, "matrix dimensions do not match");
+        let mut result = DynMatrix::<T>::zero(self.rows, other.columns);
+
+        for i in 0..self.rows {
+            for k in 0..self.columns {
+                let a = self[(i, k)];
+                // optimization: transition matrices are mostly empty, skip rows that can not contribute.
+                if a == T::ZERO {
+                    continue;
+                }
+                for j in 0..other.columns {
+                    let product = a.checked_mul(other[(k, j)])?;
+                    result[(i, j)] = result[(i, j)].checked_add(product)?;
+                }
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Multiply with another matrix, reducing every value modulo `modulus`.
+    /// Panics if the number of columns does not match the number of rows of `other`.
+    pub fn mul_mod(&self, other: &DynMatrix<T>, modulus: T) -> DynMatrix<T> {
+        assert_eq!(self.columns, other.rows, "matrix dimensions do not match");
+        let mut result = DynMatrix::<T>::zero(self.rows, other.columns);
+
+        for i in 0..self.rows {
+            for k in 0..self.columns {
+                let a = self[(i, k)];
+                if a == T::ZERO {
+                    continue;
+                }
+                for j in 0..other.columns {
+                    let product = mul_mod(a, other[(k, j)], modulus);
+                    result[(i, j)] = add_mod(result[(i, j)], product, modulus);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Reduce every value modulo `modulus`.
+    pub fn reduce(&self, modulus: T) -> Self {
+        DynMatrix {
+            values: self.values.iter().map(|x| *x % modulus).collect(),
+            ..*self
+        }
+    }
+
+    fn column(vector: &[T]) -> Self {
+        DynMatrix {
+            rows: vector.len(),
+            columns: 1,
+            values: vector.to_vec(),
+        }
+    }
+
+    /// Multiply with a column vector, `None` if any value overflows.
+    pub fn checked_apply(&self, vector: &[T]) -> Option<Vec<T>> {
+        Some(self.checked_mul(&DynMatrix::column(vector))?.values)
+    }
+
+    /// Multiply with a column vector.
+    /// Panics on overflow, see `checked_apply`.
+    pub fn apply(&self, vector: &[T]) -> Vec<T> {
+        (self * &DynMatrix::column(vector)).values
+    }
+
+    /// Multiply with a column vector, reducing every value modulo `modulus`.
+    pub fn apply_mod(&self, vector: &[T], modulus: T) -> Vec<T> {
+        self.mul_mod(&DynMatrix::column(vector), modulus).values
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut matrix = DynMatrix::zero(n, n);
+        for i in 0..n {
+            matrix[(i, i)] = T::ONE;
+        }
+        matrix
+    }
+
+    /// Raise a square matrix to the power of `exp`, `None` if any value overflows.
+    pub fn checked_pow(&self, exp: u64) -> Option<Self> {
+        assert_eq!(self.rows, self.columns, "only square matrices have powers");
+        let mut result = DynMatrix::identity(self.rows);
+        let mut base = self.clone();
+        let mut exp = exp;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.checked_mul(&base)?;
+            }
+            exp >>= 1;
+            // skip the last squaring, it is not needed and might overflow needlessly.
+            if exp > 0 {
+                base = base.checked_mul(&base)?;
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Raise a square matrix to the power of `exp`.
+    /// Panics on overflow, see `checked_pow` and `pow_mod`.
+    pub fn pow(&self, exp: u64) -> Self {
+        self.checked_pow(exp).expect("matrix power overflowed")
+    }
+
+    /// Raise a square matrix to the power of `exp`, reducing every value modulo `modulus`.
+    pub fn pow_mod(&self, exp: u64, modulus: T) -> Self {
+        assert_eq!(self.rows, self.columns, "only square matrices have powers");
+        let mut result = DynMatrix::identity(self.rows).reduce(modulus);
+        let mut base = self.reduce(modulus);
+        let mut exp = exp;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul_mod(&base, modulus);
+            }
+            base = base.mul_mod(&base, modulus);
+            exp >>= 1;
+        }
+
+        result
+    }
+}
+
+impl<T: Integer> Mul<&DynMatrix<T>> for &DynMatrix<T> {
+    type Output = DynMatrix<T>;
+
+    /// Panics on overflow, see `DynMatrix::checked_mul`.
+    fn mul(self, other: &DynMatrix<T>) -> DynMatrix<T> {
+        self.checked_mul(other).expect("matrix product overflowed")
+    }
+}
+
+/// Values are indexed by `(row, column)`.
+impl<T> Index<(usize, usize)> for DynMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &T {
+        assert!(
+            row < self.rows && column < self.columns,
+            "index is outside of matrix"
+        );
+        &self.values[row * self.columns + column]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for DynMatrix<T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+        assert!(
+            row < self.rows && column < self.columns,
+            "index is outside of matrix"
+        );
+        &mut self.values[row * self.columns + column]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci() -> Matrix<2, 2> {
+        Matrix::from_rows([[1, 1], [1, 0]])
+    }
+
+    #[test]
+    fn test_mul() {
+        let a = Matrix::<2, 3, i32>::from_rows([[1, 2, 3], [4, 5, 6]]);
+        let b = Matrix::<3, 1, i32>::from_rows([[1], [0], [-1]]);
+        assert_eq!(&a * &b, Matrix::from_rows([[-2], [-2]]));
+        assert_eq!(a.apply(&[1, 0, -1]), [-2, -2]);
+
+        let large = Matrix::<1, 2, u8>::from_rows([[16, 16]]);
+        assert_eq!(large.checked_apply(&[8, 8]), None);
+        assert_eq!(large.checked_apply(&[7, 8]), Some([240]));
+    }
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(fibonacci().pow(0), Matrix::identity());
+        assert_eq!(fibonacci().pow(10)[(0, 1)], 55);
+        assert_eq!(fibonacci().pow(92)[(0, 0)], 12200160415121876738);
+        assert_eq!(fibonacci().checked_pow(93), None);
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        assert_eq!(
+            fibonacci().pow_mod(90, 1_000_000_007),
+            fibonacci().pow(90).reduce(1_000_000_007)
+        );
+        // the pisano period for `10` is `60`.
+        assert_eq!(
+            fibonacci().pow_mod(1_000_000_000_040, 10),
+            fibonacci().pow_mod(20, 10)
+        );
+        assert_eq!(fibonacci().apply_mod(&[1, 0], 5), [1, 1]);
+    }
+
+    #[test]
+    fn test_dyn() {
+        let a = DynMatrix::<i32>::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!((a.rows(), a.columns()), (2, 3));
+        assert_eq!(a.apply(&[1, 0, -1]), [-2, -2]);
+
+        let fibonacci = DynMatrix::from_rows(vec![vec![1u64, 1], vec![1, 0]]);
+        assert_eq!(fibonacci.pow(92)[(0, 0)], 12200160415121876738);
+        assert_eq!(fibonacci.checked_pow(93), None);
+        assert_eq!(fibonacci.pow_mod(20, 10), fibonacci.pow(20).reduce(10));
+    }
+}
@@ -1,2 +1,13 @@
+pub mod animate;
+pub mod bignum;
 pub mod grid;
 pub mod math;
+pub mod matrix;
+pub mod parse;
+#[cfg(test)]
+pub mod prop;
+pub mod render;
+pub mod rng;
+#[cfg(test)]
+pub mod snapshot;
+pub mod trace;
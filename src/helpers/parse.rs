@@ -0,0 +1,472 @@
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// error returned when the input does not match the expected format.
+/// `line` and `column` start at `1` and point to the first character that could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// a cursor over a single line of input.
+/// every method consumes the token it parses or leaves the cursor untouched if it fails.
+pub struct Parser<'a> {
+    line: &'a str,
+    line_number: usize,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(line: &'a str, line_number: usize) -> Self {
+        Parser {
+            line,
+            line_number,
+            position: 0,
+        }
+    }
+
+    /// create an error for the current position.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        self.error_at(self.position, expected)
+    }
+
+    fn error_at(&self, position: usize, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line_number,
+            column: self.line[..position].chars().count() + 1,
+            expected: expected.into(),
+        }
+    }
+
+    /// get the part of the line that has not been consumed yet.
+    pub fn rest(&self) -> &'a str {
+        &self.line[self.position..]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.position == self.line.len()
+    }
+
+    /// expect the whole line to be consumed.
+    pub fn end(&self) -> ParseResult<()> {
+        if self.is_done() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+
+    /// consume `tag` if the rest of the line starts with it.
+    pub fn try_tag(&mut self, tag: &str) -> bool {
+        if self.rest().starts_with(tag) {
+            self.position += tag.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// consume `tag` or fail.
+    pub fn tag(&mut self, tag: &str) -> ParseResult<()> {
+        if self.try_tag(tag) {
+            Ok(())
+        } else {
+            Err(self.error(format!("`{}`", tag)))
+        }
+    }
+
+    /// consume characters while `predicate` matches, fails if there is none.
+    pub fn take_while(
+        &mut self,
+        expected: &str,
+        predicate: impl Fn(char) -> bool,
+    ) -> ParseResult<&'a str> {
+        let rest = self.rest();
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+
+        if len == 0 {
+            Err(self.error(expected))
+        } else {
+            self.position += len;
+            Ok(&rest[..len])
+        }
+    }
+
+    /// consume a word made up of ascii letters.
+    pub fn word(&mut self) -> ParseResult<&'a str> {
+        self.take_while("word", |c| c.is_ascii_alphabetic())
+    }
+
+    /// consume a (optionally signed) decimal integer that fits into `T`.
+    pub fn integer<T: FromStr>(&mut self) -> ParseResult<T> {
+        let start = self.position;
+        let expected = format!("integer ({})", type_name::<T>());
+
+        let rest = self.rest();
+        let sign = usize::from(rest.starts_with(['-', '+']));
+        let digits = rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - sign);
+
+        if digits == 0 {
+            return Err(self.error(expected));
+        }
+
+        match rest[..sign + digits].parse() {
+            Ok(value) => {
+                self.position += sign + digits;
+                Ok(value)
+            }
+            Err(_) => Err(self.error_at(start, expected)),
+        }
+    }
+
+    /// consume a range of two integers in the form `from..to`, e.g. `-5..10`.
+    pub fn range<T: FromStr>(&mut self) -> ParseResult<(T, T)> {
+        self.atomic(|p| {
+            let from = p.integer()?;
+            p.tag("..")?;
+            let to = p.integer()?;
+            Ok((from, to))
+        })
+    }
+
+    /// consume a `key=value` pair with the given `key`.
+    pub fn key_value<T>(
+        &mut self,
+        key: &str,
+        value: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        self.atomic(|p| {
+            p.tag(key)?;
+            p.tag("=")?;
+            value(p)
+        })
+    }
+
+    /// consume a list of at least one `item`, separated by `separator`.
+    pub fn separated<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<Vec<T>> {
+        self.atomic(|p| {
+            let mut items = vec![item(p)?];
+
+            while p.try_tag(separator) {
+                items.push(item(p)?);
+            }
+
+            Ok(items)
+        })
+    }
+
+    /// run `f`, resetting the cursor if it fails.
+    fn atomic<T>(&mut self, f: impl FnOnce(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        let start = self.position;
+        let result = f(self);
+
+        if result.is_err() {
+            self.position = start;
+        }
+
+        result
+    }
+}
+
+/// parse a single line with `f`. the whole line needs to be consumed.
+pub fn parse_line<'a, T>(
+    line: &'a str,
+    line_number: usize,
+    f: impl FnOnce(&mut Parser<'a>) -> ParseResult<T>,
+) -> ParseResult<T> {
+    let mut parser = Parser::new(line, line_number);
+    let value = f(&mut parser)?;
+    parser.end()?;
+    Ok(value)
+}
+
+/// parse every line of `input` with `f`.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    f: impl FnMut(&mut Parser<'a>) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    Block {
+        first_line: 1,
+        text: input,
+    }
+    .parse_lines(f)
+}
+
+/// parse a grid of characters with one row per line, mapping every character with `cell`.
+/// there needs to be at least one row and all rows need to have the same length.
+pub fn parse_rows<T>(
+    input: &str,
+    expected: &str,
+    cell: impl Fn(char) -> Option<T>,
+) -> ParseResult<Vec<Vec<T>>> {
+    let mut rows: Vec<Vec<T>> = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let mut p = Parser::new(line, i + 1);
+        let mut row = Vec::new();
+
+        while let Some(c) = p.rest().chars().next() {
+            row.push(cell(c).ok_or_else(|| p.error(expected))?);
+            p.position += c.len_utf8();
+        }
+
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                let expected = format!("row of length {}", first.len());
+                return Err(p.error_at(line.len().min(first.len()), expected));
+            }
+        }
+
+        rows.push(row);
+    }
+
+    match rows.first() {
+        Some(row) if !row.is_empty() => Ok(rows),
+        _ => Err(Parser::new("", 1).error(expected)),
+    }
+}
+
+/// normalize raw puzzle input so solutions do not need to care about how the file was saved.
+/// strips a leading byte order mark, converts `\r\n` line endings to `\n`, removes trailing whitespace
+/// from every line and collapses trailing blank lines into a single final newline.
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+
+    let mut lines: Vec<&str> = input.lines().map(str::trim_end).collect();
+
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+
+    if lines.is_empty() {
+        String::new()
+    } else {
+        lines.join("\n") + "\n"
+    }
+}
+
+/// a group of lines in the input, delimited by blank lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// line number of the first line of this block in the input.
+    pub first_line: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Block<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> {
+        let first_line = self.first_line;
+        self.text
+            .lines()
+            .enumerate()
+            .map(move |(i, l)| (first_line + i, l))
+    }
+
+    /// parse every line of this block with `f`.
+    pub fn parse_lines<T>(
+        &self,
+        mut f: impl FnMut(&mut Parser<'a>) -> ParseResult<T>,
+    ) -> ParseResult<Vec<T>> {
+        self.lines()
+            .map(|(line_number, line)| parse_line(line, line_number, &mut f))
+            .collect()
+    }
+}
+
+/// split `input` into blocks of lines that are separated by one or more blank lines.
+pub fn blocks(input: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        let is_blank = line.trim().is_empty();
+
+        match (start, is_blank) {
+            (None, false) => start = Some((i + 1, offset)),
+            (Some((first_line, from)), true) => {
+                blocks.push(Block {
+                    first_line,
+                    text: input[from..offset].trim_end_matches(['\n', '\r']),
+                });
+                start = None;
+            }
+            _ => {}
+        }
+
+        offset += line.len();
+    }
+
+    if let Some((first_line, from)) = start {
+        blocks.push(Block {
+            first_line,
+            text: input[from..].trim_end_matches(['\n', '\r']),
+        });
+    }
+
+    blocks
+}
+
+/// split `input` into exactly `N` blocks, e.g. a header followed by a body.
+pub fn sections<const N: usize>(input: &str) -> ParseResult<[Block<'_>; N]> {
+    let blocks = blocks(input);
+
+    if blocks.len() > N {
+        return Err(ParseError {
+            line: blocks[N].first_line,
+            column: 1,
+            expected: "end of input".into(),
+        });
+    }
+
+    blocks.try_into().map_err(|_| ParseError {
+        line: input.lines().count() + 1,
+        column: 1,
+        expected: format!("{} blocks separated by blank lines", N),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer() {
+        let mut p = Parser::new("-12,+3;x", 1);
+        assert_eq!(p.separated(",", |p| p.integer::<i32>()), Ok(vec![-12, 3]));
+        assert_eq!(p.rest(), ";x");
+
+        let mut p = Parser::new("300", 4);
+        assert_eq!(
+            p.integer::<u8>(),
+            Err(ParseError {
+                line: 4,
+                column: 1,
+                expected: "integer (u8)".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_key_value_range() {
+        let result = parse_line("x=-5..10,y=3..4", 2, |p| {
+            p.separated(",", |p| {
+                let key = p.word()?;
+                p.tag("=")?;
+                Ok((key, p.range::<i64>()?))
+            })
+        });
+        assert_eq!(result, Ok(vec![("x", (-5, 10)), ("y", (3, 4))]));
+
+        let result = parse_line("x=-5.10", 3, |p| p.key_value("x", Parser::range::<i64>));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 3, column 5: expected `..`"
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let result = parse_lines("1\n2\n3 ", |p| p.integer::<u32>());
+        assert_eq!(
+            result,
+            Err(ParseError {
+                line: 3,
+                column: 2,
+                expected: "end of line".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_rows() {
+        let digit = |c: char| c.to_digit(10);
+        assert_eq!(
+            parse_rows("12\n34", "digit", digit),
+            Ok(vec![vec![1, 2], vec![3, 4]])
+        );
+        assert_eq!(
+            parse_rows("12\n3x", "digit", digit)
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 2: expected digit"
+        );
+        assert_eq!(
+            parse_rows("12\n345", "digit", digit)
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 3: expected row of length 2"
+        );
+        assert!(parse_rows("", "digit", digit).is_err());
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "a\nb\n\n\nc\n\nd\n";
+        let blocks = blocks(input);
+        assert_eq!(
+            blocks,
+            vec![
+                Block {
+                    first_line: 1,
+                    text: "a\nb"
+                },
+                Block {
+                    first_line: 5,
+                    text: "c"
+                },
+                Block {
+                    first_line: 7,
+                    text: "d"
+                },
+            ]
+        );
+        assert_eq!(blocks[1].lines().collect::<Vec<_>>(), vec![(5, "c")]);
+    }
+
+    #[test]
+    fn test_sections() {
+        let [a, b] = sections::<2>("a\n\nb\nc\n").unwrap();
+        assert_eq!((a.text, b.first_line, b.text), ("a", 3, "b\nc"));
+
+        assert_eq!(
+            sections::<2>("a\n").unwrap_err().to_string(),
+            "line 2, column 1: expected 2 blocks separated by blank lines"
+        );
+        assert_eq!(
+            sections::<1>("a\n\nb").unwrap_err().to_string(),
+            "line 3, column 1: expected end of input"
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("\u{feff}1 \r\n\r\n 2\t\r\n\r\n\n"), "1\n\n 2\n");
+        assert_eq!(normalize("1\n2"), "1\n2\n");
+        assert_eq!(normalize("\r\n \n"), "");
+    }
+}
@@ -0,0 +1,198 @@
+//! A tiny property testing harness for the test suite.
+//! Properties are checked against many random values. The first failing value is shrunk to a
+//! smaller one that still fails before it is reported, which keeps counterexamples readable.
+//! [Wikipedia](https://en.wikipedia.org/wiki/Property_testing)
+
+use crate::helpers::rng::Rng;
+use std::fmt::Debug;
+
+/// number of random values each property is checked against.
+const CASES: u64 = 200;
+
+/// upper bound for shrinking steps, in case a shrinker does not converge.
+const MAX_SHRINKS: usize = 1000;
+
+/// A value that can be generated at random and shrunk towards a simpler value.
+pub trait Arbitrary: Clone + Debug {
+    /// Generate a value. `size` grows over the course of a run and bounds lengths and magnitudes.
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self;
+
+    /// Get simpler variants of this value, the most promising ones first.
+    fn shrink(&self) -> Vec<Self> {
+        Vec::new()
+    }
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl Arbitrary for $t {
+            fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+                // mostly small values, with the occasional value from the full range.
+                if rng.chance(0.1) {
+                    rng.next_u64() as $t
+                } else {
+                    rng.below(size + 1) as $t
+                }
+            }
+
+            fn shrink(&self) -> Vec<Self> {
+                let x = *self;
+                let mut candidates = vec![0, x / 2, x.saturating_sub(1)];
+                candidates.dedup();
+                candidates.retain(|c| *c < x);
+                candidates
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, usize);
+
+impl Arbitrary for bool {
+    fn arbitrary(rng: &mut Rng, _: usize) -> Self {
+        rng.chance(0.5)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if *self {
+            vec![false]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Vec<T> {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        let len = rng.below(size + 1);
+        (0..len).map(|_| T::arbitrary(rng, size)).collect()
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+
+        // drop the back half first, then single elements, then shrink elements in place.
+        if self.len() > 1 {
+            candidates.push(self[..self.len() / 2].to_vec());
+        }
+
+        for i in 0..self.len() {
+            let mut shorter = self.clone();
+            shorter.remove(i);
+            candidates.push(shorter);
+        }
+
+        for (i, x) in self.iter().enumerate() {
+            for smaller in x.shrink() {
+                let mut v = self.clone();
+                v[i] = smaller;
+                candidates.push(v);
+            }
+        }
+
+        candidates
+    }
+}
+
+impl<A: Arbitrary, B: Arbitrary> Arbitrary for (A, B) {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        (A::arbitrary(rng, size), B::arbitrary(rng, size))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b) = self;
+        let left = a.shrink().into_iter().map(|a| (a, b.clone()));
+        let right = b.shrink().into_iter().map(|b| (a.clone(), b));
+        left.chain(right).collect()
+    }
+}
+
+impl<A: Arbitrary, B: Arbitrary, C: Arbitrary> Arbitrary for (A, B, C) {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        (
+            A::arbitrary(rng, size),
+            B::arbitrary(rng, size),
+            C::arbitrary(rng, size),
+        )
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b, c) = self;
+        ((a.clone(), b.clone()), c.clone())
+            .shrink()
+            .into_iter()
+            .map(|((a, b), c)| (a, b, c))
+            .collect()
+    }
+}
+
+/// Find the smallest value the property does not hold for, if any.
+/// Runs are seeded, so a failure reproduces on every run.
+pub fn find_counterexample<T>(
+    generate: impl Fn(&mut Rng, usize) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> bool,
+) -> Option<T> {
+    let mut rng = Rng::new(0);
+    let mut failing = (0..CASES)
+        .map(|case| generate(&mut rng, case as usize / 2))
+        .find(|value| !property(value))?;
+
+    // greedily move to the first simpler value that still fails until none is left.
+    for _ in 0..MAX_SHRINKS {
+        match shrink(&failing).into_iter().find(|value| !property(value)) {
+            Some(smaller) => failing = smaller,
+            None => break,
+        }
+    }
+
+    Some(failing)
+}
+
+/// Check a property against random values of any type implementing `Arbitrary`.
+/// Panics with the shrunk counterexample if the property does not hold.
+pub fn check<T: Arbitrary>(property: impl Fn(&T) -> bool) {
+    check_with(T::arbitrary, T::shrink, property);
+}
+
+/// Check a property against values from a custom generator and shrinker.
+/// Panics with the shrunk counterexample if the property does not hold.
+pub fn check_with<T: Debug>(
+    generate: impl Fn(&mut Rng, usize) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> bool,
+) {
+    if let Some(value) = find_counterexample(generate, shrink, property) {
+        panic!("property does not hold for {:?}", value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrinks_to_smallest() {
+        let found = find_counterexample(u32::arbitrary, u32::shrink, |x| *x < 10);
+        assert_eq!(found, Some(10));
+
+        let found = find_counterexample(Vec::<u8>::arbitrary, Vec::shrink, |v| {
+            v.iter().all(|x| *x < 5)
+        });
+        assert_eq!(found, Some(vec![5]));
+
+        let found = find_counterexample(<(u8, u8)>::arbitrary, <(u8, u8)>::shrink, |(a, b)| {
+            a.saturating_add(*b) < 20
+        });
+        assert_eq!(found.map(|(a, b)| a as u32 + b as u32), Some(20));
+    }
+
+    #[test]
+    fn test_holding_property() {
+        assert_eq!(
+            find_counterexample(u64::arbitrary, u64::shrink, |x| x / 2 <= *x),
+            None
+        );
+        check::<Vec<bool>>(|v| v.iter().rev().rev().eq(v.iter()));
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+static ANSI_RESET: &str = "\x1b[0m";
+
+/// 256-colour ANSI codes ranging from cold (blue) to hot (red).
+static HEAT_RAMP: [u8; 16] = [
+    21, 27, 33, 39, 45, 51, 50, 48, 46, 82, 118, 190, 226, 214, 208, 196,
+];
+
+/// Render a grid of cells for debugging purposes.
+/// Implemented for the row-based grids used throughout the solutions (`Vec<Vec<T>>`, `[[T; N]; M]`, ...).
+pub trait Render {
+    type Cell;
+
+    /// Get the rows of the grid, top to bottom.
+    fn rows(&self) -> Vec<&[Self::Cell]>;
+
+    /// Render the grid to a string, mapping every cell to a glyph.
+    fn render(&self, glyph: impl Fn(&Self::Cell) -> char) -> String {
+        self.rows()
+            .iter()
+            .map(|row| row.iter().map(&glyph).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Render the grid to a string colored as a heat map.
+    /// `heat` values are normalized between the smallest and largest value found in the grid.
+    fn render_heatmap(
+        &self,
+        glyph: impl Fn(&Self::Cell) -> char,
+        heat: impl Fn(&Self::Cell) -> f64,
+    ) -> String {
+        let rows = self.rows();
+        let (min, max) = rows
+            .iter()
+            .flat_map(|row| row.iter().map(&heat))
+            .fold((f64::MAX, f64::MIN), |(min, max), h| {
+                (min.min(h), max.max(h))
+            });
+        let range = if max > min { max - min } else { 1.0 };
+
+        rows.iter()
+            .map(|row| {
+                let mut line = String::new();
+                for cell in row.iter() {
+                    let t = (heat(cell) - min) / range;
+                    let color = HEAT_RAMP[(t * (HEAT_RAMP.len() - 1) as f64).round() as usize];
+                    write!(line, "\x1b[38;5;{}m{}", color, glyph(cell)).unwrap();
+                }
+                line.push_str(ANSI_RESET);
+                line
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Encode the grid as a binary grayscale [PGM](https://netpbm.sourceforge.net/doc/pgm.html) image.
+    /// Panics if the rows differ in length, images need to be rectangular.
+    fn to_pgm(&self, shade: impl Fn(&Self::Cell) -> u8) -> Vec<u8> {
+        let rows = self.rows();
+        let mut image = netpbm_header("P5", &rows);
+        image.extend(rows.iter().flat_map(|row| row.iter().map(&shade)));
+        image
+    }
+
+    /// Encode the grid as a binary RGB [PPM](https://netpbm.sourceforge.net/doc/ppm.html) image.
+    /// Panics if the rows differ in length, images need to be rectangular.
+    fn to_ppm(&self, color: impl Fn(&Self::Cell) -> [u8; 3]) -> Vec<u8> {
+        let rows = self.rows();
+        let mut image = netpbm_header("P6", &rows);
+        image.extend(rows.iter().flat_map(|row| row.iter().flat_map(&color)));
+        image
+    }
+
+    /// Write the grid to a grayscale image file, fails if the rows differ in length.
+    fn write_pgm(
+        &self,
+        path: impl AsRef<Path>,
+        shade: impl Fn(&Self::Cell) -> u8,
+    ) -> io::Result<()> {
+        check_rectangular(&self.rows())?;
+        fs::write(path, self.to_pgm(shade))
+    }
+
+    /// Write the grid to a color image file, fails if the rows differ in length.
+    fn write_ppm(
+        &self,
+        path: impl AsRef<Path>,
+        color: impl Fn(&Self::Cell) -> [u8; 3],
+    ) -> io::Result<()> {
+        check_rectangular(&self.rows())?;
+        fs::write(path, self.to_ppm(color))
+    }
+}
+
+/// netpbm images have a single width for all rows, a jagged grid would corrupt the image.
+fn check_rectangular<T>(rows: &[&[T]]) -> io::Result<usize> {
+    let width = rows.first().map_or(0, |row| row.len());
+
+    match rows.iter().position(|row| row.len() != width) {
+        Some(y) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "row {} has {} cells, expected {} like the first row",
+                y,
+                rows[y].len(),
+                width
+            ),
+        )),
+        None => Ok(width),
+    }
+}
+
+fn netpbm_header<T>(magic: &str, rows: &[&[T]]) -> Vec<u8> {
+    let width = check_rectangular(rows).unwrap_or_else(|err| panic!("{}", err));
+    format!("{}\n{} {}\n255\n", magic, width, rows.len()).into_bytes()
+}
+
+impl<T> Render for [Vec<T>] {
+    type Cell = T;
+
+    fn rows(&self) -> Vec<&[T]> {
+        self.iter().map(|row| row.as_slice()).collect()
+    }
+}
+
+impl<T, const N: usize> Render for [[T; N]] {
+    type Cell = T;
+
+    fn rows(&self) -> Vec<&[T]> {
+        self.iter().map(|row| row.as_slice()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let grid = [vec![true, false], vec![false, true]];
+        assert_eq!(grid.render(|&x| if x { '#' } else { '.' }), "#.\n.#");
+    }
+
+    #[test]
+    fn test_render_heatmap() {
+        let grid = [[0, 9]];
+        assert_eq!(
+            grid.render_heatmap(|x| char::from_digit(*x, 10).unwrap(), |&x| x as f64),
+            "\x1b[38;5;21m0\x1b[38;5;196m9\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_to_pgm() {
+        let grid = [vec![0, 1, 2], vec![3, 4, 5]];
+        let mut expected = b"P5\n3 2\n255\n".to_vec();
+        expected.extend([0, 50, 100, 150, 200, 250]);
+        assert_eq!(grid.to_pgm(|&x| x * 50), expected);
+    }
+
+    #[test]
+    fn test_jagged_images() {
+        let grid = [vec![0, 1, 2], vec![3, 4]];
+        let err = grid.write_ppm("unused.ppm", |&x| [x; 3]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "row 1 has 2 cells, expected 3 like the first row"
+        );
+        assert!(std::panic::catch_unwind(|| grid.to_pgm(|&x| x)).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Snapshot tests compare a rendered value against a file stored in `src/snapshots/<name>.snap`.
+//! Run the tests with `UPDATE_SNAPSHOTS=1` to write the current output instead of comparing against it.
+//! Snapshots are checked in, so a behaviour change shows up as a readable diff in review.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+/// set this environment variable to (re)write snapshots instead of comparing them.
+const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+fn snapshot_path(name: &str) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join("src")
+        .join("snapshots")
+        .join(format!("{}.snap", name))
+}
+
+/// Describe the lines that differ between `expected` and `actual`.
+fn diff(expected: &str, actual: &str) -> String {
+    let (expected, actual): (Vec<&str>, Vec<&str>) =
+        (expected.lines().collect(), actual.lines().collect());
+    let mut out = String::new();
+
+    for i in 0..expected.len().max(actual.len()) {
+        let (e, a) = (expected.get(i), actual.get(i));
+        if e != a {
+            writeln!(out, "line {}:", i + 1).unwrap();
+            if let Some(e) = e {
+                writeln!(out, "-{}", e).unwrap();
+            }
+            if let Some(a) = a {
+                writeln!(out, "+{}", a).unwrap();
+            }
+        }
+    }
+
+    out
+}
+
+/// Compare `actual` against the stored snapshot `name`, or store it in update mode.
+/// Panics with a line diff if they differ or if there is no snapshot yet.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    let actual = format!("{}\n", actual.trim_end());
+
+    if env::var_os(UPDATE_VAR).is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).expect("could not write snapshot");
+        return;
+    }
+
+    let expected = match fs::read_to_string(&path) {
+        Ok(expected) => expected,
+        Err(_) => panic!(
+            "missing snapshot {}, run with `{}=1` to create it",
+            path.display(),
+            UPDATE_VAR
+        ),
+    };
+
+    if expected != actual {
+        panic!(
+            "snapshot {} does not match, run with `{}=1` to update it:\n{}",
+            name,
+            UPDATE_VAR,
+            diff(&expected, &actual)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("a\nb\nc", "a\nb\nc"), "");
+        assert_eq!(diff("a\nb", "a\nx\ny"), "line 2:\n-b\n+x\nline 3:\n+y\n");
+    }
+}
@@ -0,0 +1,84 @@
+use crate::error::{SolveError, SolveResult};
+use std::fmt::Display;
+use std::ops::ControlFlow;
+
+/// Watches a simulation. Simulations call `observe` once per step with their current state.
+/// Returning `ControlFlow::Break` stops the simulation, which then fails with `SolveError::Stopped`.
+pub trait Observer<S: ?Sized> {
+    fn observe(&mut self, state: &S) -> ControlFlow<()>;
+}
+
+/// Ignores every step. Used when solving without tracing.
+impl<S: ?Sized> Observer<S> for () {
+    fn observe(&mut self, _: &S) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// Closures can be used as ad-hoc observers, e.g. to count events in tests.
+impl<S: ?Sized, F: FnMut(&S) -> ControlFlow<()>> Observer<S> for F {
+    fn observe(&mut self, state: &S) -> ControlFlow<()> {
+        self(state)
+    }
+}
+
+/// Let `observer` see `state` and turn a request to stop into an error.
+pub fn notify<S: ?Sized>(observer: &mut impl Observer<S>, state: &S) -> SolveResult<()> {
+    match observer.observe(state) {
+        ControlFlow::Continue(()) => Ok(()),
+        ControlFlow::Break(()) => Err(SolveError::Stopped),
+    }
+}
+
+/// Prints every state it observes and optionally stops after `limit` steps.
+pub struct Tracer {
+    /// number of steps observed so far.
+    pub steps: usize,
+    limit: Option<usize>,
+}
+
+impl Tracer {
+    pub fn new(limit: Option<usize>) -> Self {
+        Tracer { steps: 0, limit }
+    }
+}
+
+impl<S: Display + ?Sized> Observer<S> for Tracer {
+    fn observe(&mut self, state: &S) -> ControlFlow<()> {
+        self.steps += 1;
+        println!("step {}:\n{}\n", self.steps, state);
+
+        match self.limit {
+            Some(limit) if self.steps >= limit => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notify() {
+        let mut seen = Vec::new();
+        let mut observer = |x: &u32| {
+            seen.push(*x);
+            if *x < 2 {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        };
+
+        assert_eq!(notify(&mut observer, &1), Ok(()));
+        assert_eq!(notify(&mut observer, &2), Err(SolveError::Stopped));
+        assert_eq!(seen, vec![1, 2]);
+        assert_eq!(notify(&mut (), &3), Ok(()));
+
+        let mut tracer = Tracer::new(Some(2));
+        assert_eq!(notify(&mut tracer, "a"), Ok(()));
+        assert_eq!(notify(&mut tracer, "b"), Err(SolveError::Stopped));
+        assert_eq!(tracer.steps, 2);
+    }
+}
@@ -1,6 +1,14 @@
 use std::env;
 use std::fs;
 
+pub mod error;
+pub mod fuzz;
+pub mod generate;
+pub mod helpers;
+pub mod solutions;
+
+/// read the puzzle input for `day` from `src/<folder>`. line endings and trailing whitespace are normalized,
+/// see `helpers::parse::normalize`.
 pub fn read_file(folder: &str, day: u8) -> String {
     let cwd = env::current_dir().unwrap();
 
@@ -10,5 +18,5 @@ pub fn read_file(folder: &str, day: u8) -> String {
         .join(format!("day{:02}.txt", day));
 
     let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    helpers::parse::normalize(&f.expect("could not open input file"))
 }
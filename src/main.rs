@@ -1,24 +1,37 @@
-use crate::solutions::*;
+use aoc::error::SolveResult;
+use aoc::generate::generate;
+use aoc::helpers::animate::Player;
+use aoc::helpers::trace::Tracer;
 use aoc::read_file;
+use aoc::solutions::*;
 use std::env;
 use std::fmt::Display;
+use std::process;
 use std::time::Instant;
 
-mod helpers;
-mod solutions;
-
 static ANSI_ITALIC: &str = "\x1b[3m";
 static ANSI_BOLD: &str = "\x1b[1m";
 static ANSI_RESET: &str = "\x1b[0m";
 
-fn print_result<T: Display>(func: impl FnOnce(&str) -> T, input: &str) {
+/// prints the result of a solution. returns `false` if the solution failed.
+fn print_result<T: Display>(func: impl FnOnce(&str) -> SolveResult<T>, input: &str) -> bool {
     let timer = Instant::now();
     let result = func(input);
     let time = timer.elapsed();
-    println!(
-        "{} {}(elapsed: {:.2?}){}",
-        result, ANSI_ITALIC, time, ANSI_RESET
-    );
+
+    match result {
+        Ok(result) => {
+            println!(
+                "{} {}(elapsed: {:.2?}){}",
+                result, ANSI_ITALIC, time, ANSI_RESET
+            );
+            true
+        }
+        Err(err) => {
+            println!("{}error:{} {}", ANSI_BOLD, ANSI_RESET, err);
+            false
+        }
+    }
 }
 
 macro_rules! solve_day {
@@ -28,22 +41,194 @@ macro_rules! solve_day {
         println!("");
         println!("🎄 {}Part 1{} 🎄", ANSI_BOLD, ANSI_RESET);
         println!("");
-        print_result(part_one, $input);
+        let part_one_ok = print_result(try_part_one, $input);
         println!("");
         println!("🎄 {}Part 2{} 🎄", ANSI_BOLD, ANSI_RESET);
         println!("");
-        print_result(part_two, $input);
+        let part_two_ok = print_result(try_part_two, $input);
         println!("");
         println!("----");
+        part_one_ok && part_two_ok
     }};
 }
 
+/// print a random input for a day: `generate <day> [size] [seed]`.
+fn print_generated(args: &[String]) {
+    let day: u8 = args[0].parse().unwrap();
+    let size: usize = args.get(1).map_or(10, |s| s.parse().unwrap());
+    let seed: u64 = args.get(2).map_or(0, |s| s.parse().unwrap());
+
+    match generate(day, size, seed) {
+        Some(input) => print!("{}", input),
+        None => {
+            println!("no generator for day: {}", day);
+            process::exit(1);
+        }
+    }
+}
+
+/// print every step of a simulation while solving part one: `<day> --trace [steps]`.
+/// stops after `steps` steps if given.
+fn trace_day(day: u8, input: &str, args: &[String]) -> bool {
+    let limit: Option<usize> = args.first().map(|s| s.parse().unwrap());
+    let mut tracer = Tracer::new(limit);
+
+    let solved = match day {
+        11 => print_result(|i| day11::observe_part_one(i, &mut tracer), input),
+        17 => print_result(|i| day17::observe_part_one(i, &mut tracer), input),
+        20 => print_result(|i| day20::observe_part_one(i, &mut tracer), input),
+        21 => print_result(|i| day21::observe_part_one(i, &mut tracer), input),
+        23 => print_result(|i| day23::observe_part_one(i, &mut tracer), input),
+        24 => print_result(|i| day24::observe_part_one(i, &mut tracer), input),
+        25 => print_result(|i| day25::observe_part_one(i, &mut tracer), input),
+        _ => {
+            println!("no trace for day: {}", day);
+            return false;
+        }
+    };
+
+    println!("traced {} steps", tracer.steps);
+    solved
+}
+
+/// print the input grid of a day as a heat map: `<day> --heatmap`.
+fn print_heatmap(day: u8, input: &str) -> bool {
+    let heatmap = match day {
+        9 => day09::heatmap(input),
+        11 => day11::heatmap(input),
+        15 => day15::heatmap(input),
+        _ => {
+            println!("no heat map for day: {}", day);
+            return false;
+        }
+    };
+
+    match heatmap {
+        Ok(heatmap) => {
+            println!("{}", heatmap);
+            true
+        }
+        Err(err) => {
+            println!("{}error:{} {}", ANSI_BOLD, ANSI_RESET, err);
+            false
+        }
+    }
+}
+
+/// play a simulation in the terminal: `animate <day> [--fps n] [--skip n]`.
+/// `--fps` sets the playback speed (default: `10`), `--skip n` only draws every `n`th step (default: `1`).
+fn animate_day(args: &[String]) -> bool {
+    let day: u8 = args[0].parse().unwrap();
+    let option = |name: &str| {
+        let position = args.iter().position(|a| a == name)?;
+        args.get(position + 1)
+    };
+    let fps: f64 = option("--fps").map_or(10.0, |s| s.parse().unwrap());
+    let skip: usize = option("--skip").map_or(1, |s| s.parse().unwrap());
+
+    let input = read_file("inputs", day);
+    let mut player = Player::new(fps, skip);
+
+    // the last frame needs to be drawn before the result, drawing clears the screen.
+    macro_rules! play {
+        ($observe:path) => {
+            print_result(
+                |i| {
+                    let result = $observe(i, &mut player);
+                    player.finish();
+                    result
+                },
+                &input,
+            )
+        };
+    }
+
+    match day {
+        11 => play!(day11::observe_part_two),
+        17 => play!(day17::observe_highest_trajectory),
+        20 => play!(day20::observe_part_two),
+        23 => play!(day23::observe_part_two),
+        25 => play!(day25::observe_part_one),
+        _ => {
+            println!("no animation for day: {}", day);
+            false
+        }
+    }
+}
+
+/// print the packets and the expression of a day 16 transmission: `disassemble [message]`.
+/// disassembles the day's input if no message is given.
+fn disassemble(args: &[String]) -> bool {
+    let input = match args.first() {
+        Some(message) => message.clone(),
+        None => read_file("inputs", 16),
+    };
+    let message = input.lines().next().unwrap_or_default();
+
+    match day16::decoder::decode(message) {
+        Ok(packet) => {
+            print!("{}", day16::disassembler::disassemble(message).unwrap());
+            println!();
+            println!("{}", day16::disassembler::expression(&packet));
+            match day16::interpreter::interpret_exact(&packet) {
+                Ok(value) => println!("= {}", value),
+                Err(err) => println!("{}error:{} {}", ANSI_BOLD, ANSI_RESET, err),
+            }
+            if let Err(err) = day16::decoder::decode_strict(message) {
+                println!(
+                    "{}warning:{} malformed transmission, {}",
+                    ANSI_BOLD, ANSI_RESET, err
+                );
+            }
+            true
+        }
+        Err(err) => {
+            println!("{}error:{} {}", ANSI_BOLD, ANSI_RESET, err);
+            false
+        }
+    }
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
+
+    if args[1] == "generate" {
+        print_generated(&args[2..]);
+        return;
+    }
+
+    if args[1] == "animate" {
+        if !animate_day(&args[2..]) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args[1] == "disassemble" {
+        if !disassemble(&args[2..]) {
+            process::exit(1);
+        }
+        return;
+    }
+
     let day: u8 = args[1].clone().parse().unwrap();
     let input = read_file("inputs", day);
 
-    match day {
+    if args.get(2).map(String::as_str) == Some("--trace") {
+        if !trace_day(day, &input, &args[3..]) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args.get(2).map(String::as_str) == Some("--heatmap") {
+        if !print_heatmap(day, &input) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let solved = match day {
         1 => solve_day!(day01, &input),
         2 => solve_day!(day02, &input),
         3 => solve_day!(day03, &input),
@@ -69,6 +254,13 @@ fn main() {
         23 => solve_day!(day23, &input),
         24 => solve_day!(day24, &input),
         25 => solve_day!(day25, &input),
-        _ => println!("day not solved: {}", day),
+        _ => {
+            println!("day not solved: {}", day);
+            false
+        }
+    };
+
+    if !solved {
+        process::exit(1);
     }
 }
@@ -1,7 +1,9 @@
+use crate::error::SolveResult;
+use crate::helpers::parse::{parse_lines, ParseResult};
 use itertools::Itertools;
 
-fn to_u32(x: &str) -> u32 {
-    x.parse::<u32>().unwrap()
+fn parse(input: &str) -> ParseResult<Vec<u32>> {
+    parse_lines(input, |p| p.integer())
 }
 
 // identify value increases across iterator by using a sliding window.
@@ -10,19 +12,18 @@ fn count_increases(it: impl Iterator<Item = u32>) -> u32 {
     win.fold(0, |acc, (a, b)| if b > a { acc + 1 } else { acc })
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let it = input.lines().map(to_u32);
-    count_increases(it)
+pub fn try_part_one(input: &str) -> SolveResult<u32> {
+    let it = parse(input)?.into_iter();
+    Ok(count_increases(it))
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let it = input
-        .lines()
-        .map(to_u32)
+pub fn try_part_two(input: &str) -> SolveResult<u32> {
+    let it = parse(input)?
+        .into_iter()
         .tuple_windows()
         .map(|(a, b, c)| a + b + c);
 
-    count_increases(it)
+    Ok(count_increases(it))
 }
 
 #[cfg(test)]
@@ -31,15 +32,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 1);
-        assert_eq!(part_one(&input), 7);
+        assert_eq!(try_part_one(&input), Ok(7));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 1);
-        assert_eq!(part_two(&input), 5);
+        assert_eq!(try_part_two(&input), Ok(5));
     }
 }
@@ -1,5 +1,14 @@
-struct Instruction<'a> {
-    direction: &'a str,
+use crate::error::SolveResult;
+use crate::helpers::parse::{parse_lines, ParseResult};
+
+enum Direction {
+    Forward,
+    Down,
+    Up,
+}
+
+struct Instruction {
+    direction: Direction,
     value: i32,
 }
 
@@ -9,51 +18,57 @@ struct Position {
     aim: i32,
 }
 
-fn to_instruction(line: &str) -> Instruction {
-    let (direction, _value) = line.split_once(' ').unwrap();
+fn parse(input: &str) -> ParseResult<Vec<Instruction>> {
+    parse_lines(input, |p| {
+        let direction = match p.word() {
+            Ok("forward") => Direction::Forward,
+            Ok("down") => Direction::Down,
+            Ok("up") => Direction::Up,
+            _ => return Err(p.error("`forward`, `down` or `up`")),
+        };
 
-    Instruction {
-        direction,
-        value: _value.parse().unwrap(),
-    }
+        p.tag(" ")?;
+
+        Ok(Instruction {
+            direction,
+            value: p.integer()?,
+        })
+    })
 }
 
 fn update_position(pos: Position, Instruction { direction, value }: Instruction) -> Position {
     match direction {
-        "forward" => Position {
+        Direction::Forward => Position {
             x: pos.x + value,
             y: pos.y + pos.aim * value,
             ..pos
         },
-        "down" => Position {
+        Direction::Down => Position {
             aim: pos.aim + value,
             ..pos
         },
-        "up" => Position {
+        Direction::Up => Position {
             aim: pos.aim - value,
             ..pos
         },
-        val => panic!("bad direction input: {}", val),
     }
 }
 
-pub fn part_one(input: &str) -> i32 {
-    let pos = input
-        .lines()
-        .map(to_instruction)
+pub fn try_part_one(input: &str) -> SolveResult<i32> {
+    let pos = parse(input)?
+        .into_iter()
         .fold(Position { x: 0, y: 0, aim: 0 }, update_position);
 
     // optimization: `aim` in part two mirrors `depth` in part one which allows us to reuse the positioning logic.
-    pos.x * pos.aim
+    Ok(pos.x * pos.aim)
 }
 
-pub fn part_two(input: &str) -> i32 {
-    let pos = input
-        .lines()
-        .map(to_instruction)
+pub fn try_part_two(input: &str) -> SolveResult<i32> {
+    let pos = parse(input)?
+        .into_iter()
         .fold(Position { x: 0, y: 0, aim: 0 }, update_position);
 
-    pos.x * pos.y
+    Ok(pos.x * pos.y)
 }
 
 #[cfg(test)]
@@ -62,15 +77,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 2);
-        assert_eq!(part_one(&input), 150);
+        assert_eq!(try_part_one(&input), Ok(150));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 2);
-        assert_eq!(part_two(&input), 900);
+        assert_eq!(try_part_two(&input), Ok(900));
     }
 }
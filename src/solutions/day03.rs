@@ -1,6 +1,21 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::parse_rows;
 use itertools::Itertools;
 use std::collections::HashMap;
 
+/// check that every line is a binary number of the same width that fits into a `u32`.
+fn validate(input: &str) -> SolveResult<Vec<&str>> {
+    let rows = parse_rows(input, "`0` or `1`", |c| {
+        matches!(c, '0' | '1').then_some(())
+    })?;
+
+    if rows[0].len() > 32 {
+        return Err(SolveError::invalid("numbers can have at most 32 bits"));
+    }
+
+    Ok(input.lines().collect())
+}
+
 pub fn arr_to_int(bits: &[bool]) -> u32 {
     bits.iter().fold(0, |acc, &b| acc * 2 + (b as u32))
 }
@@ -9,14 +24,16 @@ pub fn str_to_int(str: &str) -> u32 {
     u32::from_str_radix(str, 2).unwrap()
 }
 
-pub fn part_one(input: &str) -> u32 {
+pub fn try_part_one(input: &str) -> SolveResult<u64> {
+    let lines = validate(input)?;
+
     // counter that maps character indices to signed integers.
     // we can later calculate the gamma value for a given index by checking the entry's sign.
     let mut counter: HashMap<usize, i32> = HashMap::new();
 
     // for every character in a line:
     // increment (1) or decrement (0) the counter entry for this index.
-    input.lines().for_each(|l| {
+    lines.iter().for_each(|l| {
         l.chars().enumerate().for_each(|(i, c)| {
             let val = counter.entry(i).or_default();
             *val += if c == '1' { 1 } else { -1 };
@@ -33,11 +50,12 @@ pub fn part_one(input: &str) -> u32 {
     // derive epsilon by flipping each bit of gamma.
     let epsilon = gamma.iter().map(|b| !(*b)).collect_vec();
 
-    arr_to_int(&gamma) * arr_to_int(&epsilon)
+    // both rates fit into 32 bits, so their product always fits into 64 bits.
+    Ok(arr_to_int(&gamma) as u64 * arr_to_int(&epsilon) as u64)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let lines = input.lines().collect_vec();
+pub fn try_part_two(input: &str) -> SolveResult<u64> {
+    let lines = validate(input)?;
 
     let oxy_rating =
         find_line_by_bit_criteria(|a, b| if a.len() >= b.len() { a } else { b }, &lines);
@@ -45,7 +63,7 @@ pub fn part_two(input: &str) -> u32 {
     let co2_rating =
         find_line_by_bit_criteria(|a, b| if a.len() >= b.len() { b } else { a }, &lines);
 
-    str_to_int(oxy_rating) * str_to_int(co2_rating)
+    Ok(str_to_int(oxy_rating) as u64 * str_to_int(co2_rating) as u64)
 }
 
 fn find_line_by_bit_criteria<'a>(
@@ -54,15 +72,21 @@ fn find_line_by_bit_criteria<'a>(
 ) -> &'a str {
     let mut i = 0;
     let mut survivors = candidates.to_vec();
+    let width = survivors[0].len();
 
-    while survivors.len() > 1 {
+    // duplicate lines can not be told apart, stop once every column was considered.
+    while survivors.len() > 1 && i < width {
         // partition lines by the dominant bit (1 or 0) in column `i`.
         let (one_dominant, zero_dominant): (Vec<&str>, Vec<&str>) = survivors
             .iter()
             .partition(|s| s.chars().nth(i).unwrap() == '1');
 
         // determine which group should be continued with and assign it as survivors, discarding the rest.
-        survivors = bit_criteria(one_dominant, zero_dominant);
+        // if every survivor has the same bit, the criteria can not discard any of them.
+        let next = bit_criteria(one_dominant, zero_dominant);
+        if !next.is_empty() {
+            survivors = next;
+        }
         i += 1;
     }
 
@@ -75,15 +99,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 3);
-        assert_eq!(part_one(&input), 198);
+        assert_eq!(try_part_one(&input), Ok(198));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 3);
-        assert_eq!(part_two(&input), 230);
+        assert_eq!(try_part_two(&input), Ok(230));
     }
 }
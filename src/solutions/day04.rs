@@ -1,3 +1,6 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::grid::Grid;
+use crate::helpers::parse::{blocks, Block, ParseError, ParseResult};
 use std::collections::{HashMap, HashSet};
 
 static BOARD_SIZE: usize = 5;
@@ -8,58 +11,71 @@ struct Board {
     nums: HashSet<u32>,
     // holds both `horizontal` and `vertical` rows of ths board.
     // optimization: pre-calculate a 'transposed' set of rows & columns once in the beginning.
-    // optimization: use signed integers for keys (- for column ids) to avoid expensive string operations.
-    rows: HashMap<i32, Vec<u32>>,
+    rows: Vec<Vec<u32>>,
 }
 
 type Boards = HashMap<usize, Board>;
 
-pub fn str_to_u32(s: &str) -> u32 {
-    s.trim().parse().unwrap()
-}
+fn to_draw(block: &Block) -> ParseResult<Vec<u32>> {
+    let draw = block.parse_lines(|p| p.separated(",", |p| p.integer()))?;
 
-fn to_draw(line: &str) -> Vec<u32> {
-    line.split(',').map(str_to_u32).collect()
+    match draw.as_slice() {
+        [draw] => Ok(draw.clone()),
+        _ => Err(ParseError {
+            line: block.first_line + 1,
+            column: 1,
+            expected: "blank line after draw".into(),
+        }),
+    }
 }
 
-fn to_board(lines: &[&str]) -> Board {
-    lines.iter().enumerate().fold(
-        Board {
-            nums: HashSet::new(),
-            rows: HashMap::new(),
-        },
-        |mut board, (_row, l)| {
-            l.trim()
-                .split_whitespace()
-                .enumerate()
-                .for_each(|(_col, s)| {
-                    let parsed: u32 = str_to_u32(s);
-                    let col: i32 = _col as i32;
-                    let row: i32 = _row as i32;
-                    board.nums.insert(parsed);
-                    board.rows.entry(-(col + 1)).or_default().push(parsed);
-                    board.rows.entry(row + 1).or_default().push(parsed);
-                });
-
-            board
-        },
-    )
+fn to_board(block: &Block) -> SolveResult<Board> {
+    let rows: Vec<Vec<u32>> = block.parse_lines(|p| {
+        let mut row = Vec::new();
+        loop {
+            let _ = p.take_while("space", |c| c == ' ');
+            if p.is_done() {
+                return Ok(row);
+            }
+            row.push(p.integer()?);
+        }
+    })?;
+
+    if rows.len() != BOARD_SIZE || rows.iter().any(|r| r.len() != BOARD_SIZE) {
+        return Err(SolveError::invalid(format!(
+            "board starting at line {} is not {}x{}",
+            block.first_line, BOARD_SIZE, BOARD_SIZE
+        )));
+    }
+
+    let grid = Grid::from_rows(rows);
+    let columns = grid.transpose();
+
+    Ok(Board {
+        nums: grid.cells().copied().collect(),
+        rows: grid
+            .rows()
+            .chain(columns.rows())
+            .map(|r| r.to_vec())
+            .collect(),
+    })
 }
 
-fn to_boards<'a>(lines: impl Iterator<Item = &'a str>) -> Boards {
-    let mut boards = HashMap::new();
+fn parse(input: &str) -> SolveResult<(Vec<u32>, Boards)> {
+    let blocks = blocks(input);
+    let (first, rest) = blocks
+        .split_first()
+        .ok_or_else(|| SolveError::invalid("input is empty"))?;
 
-    lines
-        .filter(|l| !l.is_empty())
-        .collect::<Vec<&str>>()
-        .chunks(BOARD_SIZE)
+    let draw = to_draw(first)?;
+    let boards = rest
+        .iter()
         .map(to_board)
         .enumerate()
-        .for_each(|(i, b)| {
-            boards.insert(i, b);
-        });
+        .map(|(i, b)| b.map(|b| (i, b)))
+        .collect::<SolveResult<Boards>>()?;
 
-    boards
+    Ok((draw, boards))
 }
 
 fn find_winners(draw: &[u32], boards: &Boards, i: usize) -> Vec<(usize, u32)> {
@@ -72,7 +88,7 @@ fn find_winners(draw: &[u32], boards: &Boards, i: usize) -> Vec<(usize, u32)> {
             // optimization: skip board processing if it does not contain the drawn number.
             b.nums.contains(current_draw)
             // check if any row consists of crossed numbers only.
-            && b.rows.values().any(|v| v.iter().all(|n| draws.contains(n)))
+            && b.rows.iter().any(|v| v.iter().all(|n| draws.contains(n)))
         })
         .map(|(key, b)| {
             let uncrossed_nums: u32 = b.nums.iter().filter(|n| !draws.contains(n)).sum();
@@ -81,21 +97,18 @@ fn find_winners(draw: &[u32], boards: &Boards, i: usize) -> Vec<(usize, u32)> {
         .collect()
 }
 
-fn find_first_winner(draw: &[u32], boards: &mut Boards, i: usize) -> u32 {
-    let winners = find_winners(draw, boards, i);
-
-    if winners.is_empty() {
-        find_first_winner(draw, boards, i + 1)
-    } else {
-        let (_, score) = winners.first().unwrap();
-        *score
-    }
+fn find_first_winner(draw: &[u32], boards: &Boards) -> Option<u32> {
+    (BOARD_SIZE..=draw.len()).find_map(|i| {
+        find_winners(draw, boards, i)
+            .first()
+            .map(|(_, score)| *score)
+    })
 }
 
-fn find_last_winner(draw: &[u32], boards: &mut Boards) -> u32 {
+fn find_last_winner(draw: &[u32], boards: &mut Boards) -> Option<u32> {
     let mut winners: Vec<u32> = Vec::new();
 
-    for i in BOARD_SIZE..draw.len() {
+    for i in BOARD_SIZE..=draw.len() {
         find_winners(draw, boards, i)
             .iter()
             .for_each(|(key, score)| {
@@ -104,23 +117,19 @@ fn find_last_winner(draw: &[u32], boards: &mut Boards) -> u32 {
             });
     }
 
-    *winners.last().unwrap()
+    winners.last().copied()
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let mut lines = input.lines();
-    let draw: Vec<u32> = to_draw(lines.next().unwrap());
-    let mut boards = to_boards(lines);
+pub fn try_part_one(input: &str) -> SolveResult<u32> {
+    let (draw, boards) = parse(input)?;
 
-    find_first_winner(&draw, &mut boards, BOARD_SIZE)
+    find_first_winner(&draw, &boards).ok_or_else(|| SolveError::no_solution("no board wins"))
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let mut lines = input.lines();
-    let draw: Vec<u32> = to_draw(lines.next().unwrap());
-    let mut boards = to_boards(lines);
+pub fn try_part_two(input: &str) -> SolveResult<u32> {
+    let (draw, mut boards) = parse(input)?;
 
-    find_last_winner(&draw, &mut boards)
+    find_last_winner(&draw, &mut boards).ok_or_else(|| SolveError::no_solution("no board wins"))
 }
 
 #[cfg(test)]
@@ -129,15 +138,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 4);
-        assert_eq!(part_one(&input), 4512);
+        assert_eq!(try_part_one(&input), Ok(4512));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 4);
-        assert_eq!(part_two(&input), 1924);
+        assert_eq!(try_part_two(&input), Ok(1924));
     }
 }
@@ -1,3 +1,5 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::{parse_lines, ParseResult, Parser};
 use std::{collections::HashMap, convert::TryInto};
 
 struct Point {
@@ -10,7 +12,7 @@ type Grid = HashMap<(i32, i32), u32>;
 
 trait PointGrid {
     fn add_point(&mut self, x: i32, y: i32);
-    fn add_points(&mut self, line: &str, skip_diagonals: bool);
+    fn add_points(&mut self, line: &Line, skip_diagonals: bool);
     fn overlaps(&self) -> u32;
 }
 
@@ -19,9 +21,7 @@ impl PointGrid for Grid {
         *self.entry((x, y)).or_default() += 1;
     }
 
-    fn add_points(&mut self, line: &str, skip_diagonals: bool) {
-        let (p1, p2) = parse_line(line);
-
+    fn add_points(&mut self, (p1, p2): &Line, skip_diagonals: bool) {
         if skip_diagonals && (p1.x != p2.x && p1.y != p2.y) {
             return;
         }
@@ -33,7 +33,7 @@ impl PointGrid for Grid {
         let dy = (p2.y - p1.y).signum();
 
         while (x, y) != (p2.x + dx, p2.y + dy) {
-            *self.entry((x, y)).or_default() += 1;
+            self.add_point(x, y);
             x += dx;
             y += dy;
         }
@@ -48,28 +48,44 @@ impl PointGrid for Grid {
     }
 }
 
-fn parse_line(l: &str) -> Line {
-    let mut parts = l.split(" -> ").map(|p| {
-        let mut nums = p.split(',').map(|x| x.parse().unwrap());
-        Point {
-            x: nums.next().unwrap(),
-            y: nums.next().unwrap(),
-        }
-    });
+fn parse_point(p: &mut Parser) -> ParseResult<Point> {
+    let x = p.integer()?;
+    p.tag(",")?;
+    let y = p.integer()?;
+    Ok(Point { x, y })
+}
 
-    (parts.next().unwrap(), parts.next().unwrap())
+fn parse(input: &str) -> ParseResult<Vec<Line>> {
+    parse_lines(input, |p| {
+        let from = parse_point(p)?;
+        p.tag(" -> ")?;
+        let to = parse_point(p)?;
+        Ok((from, to))
+    })
 }
 
-pub fn part_one(input: &str) -> u32 {
+pub fn try_part_one(input: &str) -> SolveResult<u32> {
     let mut grid: Grid = HashMap::new();
-    input.lines().for_each(|l| grid.add_points(l, true));
-    grid.overlaps()
+    parse(input)?.iter().for_each(|l| grid.add_points(l, true));
+    Ok(grid.overlaps())
 }
 
-pub fn part_two(input: &str) -> u32 {
+pub fn try_part_two(input: &str) -> SolveResult<u32> {
+    let lines = parse(input)?;
+
+    // lines are walked one step at a time, which only terminates for horizontal, vertical and 45° lines.
+    if let Some(i) = lines.iter().position(|(p1, p2)| {
+        p1.x != p2.x && p1.y != p2.y && (p2.x - p1.x).abs() != (p2.y - p1.y).abs()
+    }) {
+        return Err(SolveError::invalid(format!(
+            "line {} is neither horizontal, vertical nor diagonal",
+            i + 1
+        )));
+    }
+
     let mut grid: Grid = HashMap::new();
-    input.lines().for_each(|l| grid.add_points(l, false));
-    grid.overlaps()
+    lines.iter().for_each(|l| grid.add_points(l, false));
+    Ok(grid.overlaps())
 }
 
 #[cfg(test)]
@@ -78,15 +94,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 5);
-        assert_eq!(part_one(&input), 5);
+        assert_eq!(try_part_one(&input), Ok(5));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 5);
-        assert_eq!(part_two(&input), 12);
+        assert_eq!(try_part_two(&input), Ok(12));
     }
 }
@@ -1,64 +1,88 @@
-static REPRO_INTERVAL_INITIAL: usize = 9;
-static REPRO_INTERVAL: usize = 7;
-
-fn get_og_fishes(input: &str) -> Vec<usize> {
-    input
-        .split(',')
-        .map(|s| s.trim().parse().unwrap())
-        .collect()
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::matrix::Matrix;
+use crate::helpers::parse::parse_line;
+
+const REPRO_INTERVAL_INITIAL: usize = 9;
+const REPRO_INTERVAL: usize = 7;
+
+type Population = [u64; REPRO_INTERVAL_INITIAL];
+
+/// count fishes by the number of days left until they reproduce.
+fn get_og_fishes(input: &str) -> SolveResult<Population> {
+    let timers = parse_line(input.trim_end(), 1, |p| {
+        p.separated(",", |p| p.integer::<usize>())
+    })?;
+
+    timers
+        .into_iter()
+        .try_fold([0; REPRO_INTERVAL_INITIAL], |mut acc, timer| {
+            if timer >= REPRO_INTERVAL_INITIAL {
+                return Err(SolveError::invalid(format!(
+                    "timer {} is larger than {}",
+                    timer,
+                    REPRO_INTERVAL_INITIAL - 1
+                )));
+            }
+            acc[timer] += 1;
+            Ok(acc)
+        })
 }
 
-fn project_population(members: Vec<usize>, generations: &mut [usize]) -> usize {
-    let interval_count = generations.len();
-    let mut pop = members.len();
-
-    // for each fish in the OG generation, spawn an offspring at {rest_timer}.
-    // we have to project their adult life as well since OGs continue reproducing.
-    members.iter().for_each(|f| {
-        generations[*f] += 1;
-        project_adult_generation(generations, 1, *f, interval_count);
-    });
-
-    // a generation of fishes hatches every day.
-    // we can project them as a whole since there is no variance in repro. rate in a generation.
-    for i in 0..interval_count {
-        let generation_size = generations[i];
-        // add hatched fishes to the population counter.
-        pop += generation_size;
-
-        // initial reproduction is delayed for hatched fishes. we handle it with a special case.
-        let adults_at = i + REPRO_INTERVAL_INITIAL;
-
-        if adults_at < interval_count {
-            generations[adults_at] += generation_size;
-            // Once fishes are adults, we can project the rest of {interval_count} in a loop.
-            project_adult_generation(generations, generation_size, adults_at, interval_count);
-        }
+/// every day, all timers count down by one. fishes at `0` reset their timer and spawn a new fish.
+/// this is a linear recurrence, so we can describe a day as a matrix that transforms a population into the next.
+/// the population after `n` days then is `transition^n * population`, which takes `log(n)` matrix products.
+fn transition() -> Matrix<REPRO_INTERVAL_INITIAL, REPRO_INTERVAL_INITIAL> {
+    let mut matrix = Matrix::zero();
+
+    for timer in 1..REPRO_INTERVAL_INITIAL {
+        matrix[(timer - 1, timer)] = 1;
     }
 
-    pop
+    matrix[(REPRO_INTERVAL - 1, 0)] = 1;
+    matrix[(REPRO_INTERVAL_INITIAL - 1, 0)] = 1;
+    matrix
 }
 
-fn project_adult_generation(
-    generations: &mut [usize],
-    generation_size: usize,
-    current_interval: usize,
-    interval_count: usize,
-) {
-    let mut spawns_at = current_interval + REPRO_INTERVAL;
-    // increase the generation_size at {interval} by the current generation_size for rest of observed interval.
-    while spawns_at < interval_count {
-        generations[spawns_at] += generation_size;
-        spawns_at += REPRO_INTERVAL;
-    }
+fn project_population(members: &Population, days: u64) -> SolveResult<u64> {
+    transition()
+        .checked_pow(days)
+        .and_then(|matrix| matrix.checked_apply(members))
+        .and_then(|timers| {
+            timers
+                .into_iter()
+                .try_fold(0u64, |acc, n| acc.checked_add(n))
+        })
+        .ok_or_else(|| SolveError::invalid("population does not fit into u64"))
 }
 
-pub fn part_one(input: &str) -> usize {
-    project_population(get_og_fishes(input), &mut [0; 80])
+pub fn try_part_one(input: &str) -> SolveResult<u64> {
+    project_population(&get_og_fishes(input)?, 80)
 }
 
-pub fn part_two(input: &str) -> usize {
-    project_population(get_og_fishes(input), &mut [0; 256])
+pub fn try_part_two(input: &str) -> SolveResult<u64> {
+    project_population(&get_og_fishes(input)?, 256)
+}
+
+/// simulates every fish individually. only feasible for a few days.
+#[cfg(test)]
+mod naive {
+    pub fn project_population(input: &str, days: u64) -> u64 {
+        let mut fishes: Vec<u8> = input
+            .trim()
+            .split(',')
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        for _ in 0..days {
+            let spawned = fishes.iter().filter(|f| **f == 0).count();
+            for fish in fishes.iter_mut() {
+                *fish = if *fish == 0 { 6 } else { *fish - 1 };
+            }
+            fishes.extend(std::iter::repeat_n(8, spawned));
+        }
+
+        fishes.len() as u64
+    }
 }
 
 #[cfg(test)]
@@ -67,15 +91,43 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 6);
-        assert_eq!(part_one(&input), 5934);
+        assert_eq!(try_part_one(&input), Ok(5934));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 6);
-        assert_eq!(part_two(&input), 26984457539);
+        assert_eq!(try_part_two(&input), Ok(26984457539));
+    }
+
+    #[test]
+    fn test_naive() {
+        use crate::generate;
+        use crate::helpers::rng::Rng;
+
+        for seed in 0..10 {
+            let input = generate::day06(&mut Rng::new(seed), 5);
+            let population = get_og_fishes(&input).unwrap();
+
+            for days in [0, 1, 7, 9, 18, 60] {
+                assert_eq!(
+                    project_population(&population, days),
+                    Ok(naive::project_population(&input, days))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let population = get_og_fishes("3,4,3,1,2").unwrap();
+        assert!(project_population(&population, 400).is_ok());
+        assert_eq!(
+            project_population(&population, 10_000),
+            Err(SolveError::invalid("population does not fit into u64"))
+        );
     }
 }
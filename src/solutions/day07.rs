@@ -1,37 +1,37 @@
-use crate::helpers::math::{median, nth_triangular};
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::math::{checked_nth_triangular, mean, median};
+use crate::helpers::parse::{parse_line, ParseResult};
 
-fn parse(input: &str) -> Vec<u64> {
-    input
-        .lines()
-        .next()
-        .unwrap()
-        .split(',')
-        .map(|x| x.parse().unwrap())
-        .collect()
+fn parse(input: &str) -> ParseResult<Vec<u64>> {
+    parse_line(input.trim_end(), 1, |p| p.separated(",", |p| p.integer()))
 }
 
-pub fn part_one(input: &str) -> u64 {
-    let mut positions = parse(input);
+pub fn try_part_one(input: &str) -> SolveResult<u64> {
+    let mut positions = parse(input)?;
     let median = median(&mut positions);
     positions
         .iter()
-        .map(|x| (*x as i32 - median as i32).abs() as u64)
-        .sum()
+        .try_fold(0u64, |acc, x| acc.checked_add(x.abs_diff(median)))
+        .ok_or_else(|| SolveError::invalid("fuel cost does not fit into 64 bits"))
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let mut positions = parse(input);
-    positions.sort_unstable();
+pub fn try_part_two(input: &str) -> SolveResult<u64> {
+    let positions = parse(input)?;
+    // the fuel cost is convex and its continuous minimum lies within `0.5` of the mean position.
+    // this leaves at most three integer candidates to check instead of every position.
+    // `separated` parses at least one position, so the mean is always defined.
+    let mean = mean(&positions).unwrap();
+    let from = (mean - 0.5).floor() as u64;
+    let to = (mean + 0.5).ceil() as u64;
 
-    (0..*positions.last().unwrap())
-        .map(|i| {
-            positions
-                .iter()
-                .map(|p| nth_triangular((*p as i32 - i as i32).abs() as u64))
-                .sum()
+    (from..=to)
+        .filter_map(|i| {
+            positions.iter().try_fold(0u64, |acc, p| {
+                acc.checked_add(checked_nth_triangular(p.abs_diff(i))?)
+            })
         })
         .min()
-        .unwrap()
+        .ok_or_else(|| SolveError::invalid("fuel cost does not fit into 64 bits"))
 }
 
 #[cfg(test)]
@@ -40,15 +40,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 7);
-        assert_eq!(part_one(&input), 37);
+        assert_eq!(try_part_one(&input), Ok(37));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 7);
-        assert_eq!(part_two(&input), 168);
+        assert_eq!(try_part_two(&input), Ok(168));
     }
 }
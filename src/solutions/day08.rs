@@ -1,21 +1,49 @@
 use std::collections::HashMap;
 
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::{parse_lines, ParseResult, Parser};
 use itertools::Itertools;
 
-pub fn part_one(input: &str) -> usize {
-    input
-        .lines()
-        .flat_map(|l| {
-            l.split(" | ").last().unwrap().split(' ').filter(|s| {
+type Pattern = Vec<char>;
+
+struct Entry {
+    signal: Vec<Pattern>,
+    outputs: Vec<Pattern>,
+}
+
+fn parse_patterns(p: &mut Parser, count: usize) -> ParseResult<Vec<Pattern>> {
+    (0..count)
+        .map(|i| {
+            if i > 0 {
+                p.tag(" ")?;
+            }
+            p.take_while("segments `a-g`", |c| ('a'..='g').contains(&c))
+                .map(|s| s.chars().collect())
+        })
+        .collect()
+}
+
+fn parse(input: &str) -> ParseResult<Vec<Entry>> {
+    parse_lines(input, |p| {
+        let signal = parse_patterns(p, 10)?;
+        p.tag(" | ")?;
+        let outputs = parse_patterns(p, 4)?;
+        Ok(Entry { signal, outputs })
+    })
+}
+
+pub fn try_part_one(input: &str) -> SolveResult<usize> {
+    Ok(parse(input)?
+        .iter()
+        .flat_map(|e| {
+            e.outputs.iter().filter(|s| {
                 let len = s.len();
                 (2..=4).contains(&len) || len == 7
             })
         })
-        .count()
+        .count())
 }
 
-type Pattern = Vec<char>;
-
 /// The display is made up of segments `a-g` mapped as follows:
 ///  aaaa
 /// b    c
@@ -27,36 +55,39 @@ type Pattern = Vec<char>;
 type Display = HashMap<char, char>;
 
 trait DisplayMethods {
-    fn decode(&self, pattern: &[char]) -> char;
+    fn decode(&self, pattern: &[char]) -> Option<char>;
 }
 
 impl DisplayMethods for Display {
     /// Once a display is fully reconstructed, we can decode digits with it.
-    /// Individual digits are returned as strings since we need to join 4 digits in the caller.
-    fn decode(&self, pattern: &[char]) -> char {
+    /// Individual digits are returned as chars since we need to join 4 digits in the caller.
+    /// Returns `None` if the pattern does not map to a digit.
+    fn decode(&self, pattern: &[char]) -> Option<char> {
         if is_one(pattern) {
-            '1'
+            Some('1')
         } else if is_four(pattern) {
-            '4'
+            Some('4')
         } else if is_seven(pattern) {
-            '7'
+            Some('7')
         } else if is_eight(pattern) {
-            '8'
+            Some('8')
         } else {
             let displayed: String = pattern
                 .iter()
-                .map(|c| self.get(c).unwrap())
+                .map(|c| self.get(c))
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
                 .sorted_unstable()
                 .collect();
 
             match displayed.as_ref() {
-                "abcefg" => '0',
-                "acdeg" => '2',
-                "acdfg" => '3',
-                "abdfg" => '5',
-                "abdefg" => '6',
-                "abcdfg" => '9',
-                val => panic!("unexpected decoded pattern: {}", val),
+                "abcefg" => Some('0'),
+                "acdeg" => Some('2'),
+                "acdfg" => Some('3'),
+                "abdfg" => Some('5'),
+                "abdefg" => Some('6'),
+                "abcdfg" => Some('9'),
+                _ => None,
             }
         }
     }
@@ -90,77 +121,77 @@ fn is_zero(p: &[char], four: &[char]) -> bool {
     four.iter().any(|c| !p.contains(c))
 }
 
-/// Helper trait to make `.find()`ing the first digits less verbose.
-fn find_by(signal: &[Vec<char>], find_fn: impl Fn(&[char]) -> bool) -> Pattern {
-    signal.iter().find(|x| find_fn(x)).unwrap().to_owned()
+/// Helper to make `.find()`ing the first digits less verbose.
+fn find_by(signal: &[Pattern], find_fn: impl Fn(&[char]) -> bool) -> Option<Pattern> {
+    signal.iter().find(|x| find_fn(x)).cloned()
 }
 
-pub fn part_two(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|l| {
-            let patterns: Vec<Pattern> = l
-                .replace(" |", "")
-                .split(' ')
-                .map(|s| s.chars().collect())
-                .collect();
-
-            let signal = patterns[0..10].to_vec();
-            let outputs = patterns[10..14].to_vec();
-
-            let mut display = Display::new();
-
-            let one = find_by(&signal, is_one);
-            let seven = find_by(&signal, is_seven);
-            let four = find_by(&signal, is_four);
-            let eight = find_by(&signal, is_eight);
-
-            // once we know `c` and `f`, we can isolate `a` by looking at `7`
-            display.insert(*seven.iter().find(|c| !&one.contains(c)).unwrap(), 'a');
-
-            // at this point, we can decode the full signal by looking at six-segment components.
-            for p in signal.iter().filter(|x| x.len() == 6) {
-                if is_six(p, &one) {
-                    for c in &one {
-                        if p.contains(c) {
-                            display.insert(*c, 'f');
-                        } else {
-                            display.insert(*c, 'c');
-                        }
-                    }
-                } else if is_zero(p, &four) {
-                    for c in &four {
-                        if !p.contains(c) {
-                            display.insert(*c, 'd');
-                        } else if !&one.contains(c) {
-                            display.insert(*c, 'b');
-                        }
-                    }
+/// Reconstruct the wiring of a display from its signal and decode the output value.
+/// Returns `None` if the signal is inconsistent.
+fn decode_entry(Entry { signal, outputs }: &Entry) -> Option<u32> {
+    let mut display = Display::new();
+
+    let one = find_by(signal, is_one)?;
+    let seven = find_by(signal, is_seven)?;
+    let four = find_by(signal, is_four)?;
+    let eight = find_by(signal, is_eight)?;
+
+    // once we know `c` and `f`, we can isolate `a` by looking at `7`
+    display.insert(*seven.iter().find(|c| !&one.contains(c))?, 'a');
+
+    // at this point, we can decode the full signal by looking at six-segment components.
+    for p in signal.iter().filter(|x| x.len() == 6) {
+        if is_six(p, &one) {
+            for c in &one {
+                if p.contains(c) {
+                    display.insert(*c, 'f');
                 } else {
-                    for c in &eight {
-                        if !p.contains(c) {
-                            display.insert(*c, 'e');
-                        }
-                    }
+                    display.insert(*c, 'c');
                 }
             }
-
-            // whatever segment is left over maps to the last needed segment `g`.
-            // we can use `eight` to identify it since it has all segments.
+        } else if is_zero(p, &four) {
+            for c in &four {
+                if !p.contains(c) {
+                    display.insert(*c, 'd');
+                } else if !&one.contains(c) {
+                    display.insert(*c, 'b');
+                }
+            }
+        } else {
             for c in &eight {
-                if !(display.contains_key(c)) {
-                    display.insert(*c, 'g');
+                if !p.contains(c) {
+                    display.insert(*c, 'e');
                 }
             }
+        }
+    }
+
+    // whatever segment is left over maps to the last needed segment `g`.
+    // we can use `eight` to identify it since it has all segments.
+    for c in &eight {
+        if !(display.contains_key(c)) {
+            display.insert(*c, 'g');
+        }
+    }
 
-            // the display is ready for decoding now.
-            // We decode the 4-digit number to a string and then parse it to an int.
-            let num = outputs.iter().fold(String::new(), |mut acc, p| {
-                acc.push(display.decode(p));
-                acc
-            });
+    // the display is ready for decoding now.
+    // We decode the 4-digit number to a string and then parse it to an int.
+    let num = outputs
+        .iter()
+        .map(|p| display.decode(p))
+        .collect::<Option<String>>()?;
 
-            num.parse::<u32>().unwrap()
+    num.parse::<u32>().ok()
+}
+
+pub fn try_part_two(input: &str) -> SolveResult<u32> {
+    parse(input)?
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            decode_entry(entry).ok_or_else(|| {
+                SolveError::invalid(format!("line {}: signal can not be decoded", i + 1))
+            })
         })
         .sum()
 }
@@ -171,15 +202,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 8);
-        assert_eq!(part_one(&input), 26);
+        assert_eq!(try_part_one(&input), Ok(26));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 8);
-        assert_eq!(part_two(&input), 61229);
+        assert_eq!(try_part_two(&input), Ok(61229));
     }
 }
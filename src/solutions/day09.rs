@@ -1,3 +1,8 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::grid::{Connectivity, Grid};
+use crate::helpers::parse::{parse_rows, ParseResult};
+use crate::helpers::render::Render;
+
 type Matrix = Vec<Vec<u32>>;
 
 #[derive(Clone, Copy, PartialEq)]
@@ -7,15 +12,8 @@ struct Point {
     val: u32,
 }
 
-fn parse(input: &str) -> Matrix {
-    input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| c.to_digit(10).unwrap())
-                .collect::<Vec<u32>>()
-        })
-        .collect()
+fn parse(input: &str) -> ParseResult<Matrix> {
+    parse_rows(input, "digit", |c| c.to_digit(10))
 }
 
 fn surounding_points(matrix: &[Vec<u32>], p: &Point) -> [Point; 4] {
@@ -75,40 +73,38 @@ fn get_minimums(matrix: &[Vec<u32>]) -> Vec<Point> {
     minimums
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let matrix = parse(input);
-    get_minimums(&matrix).iter().map(|p| p.val + 1).sum()
+/// Render the height map as a terminal heat map, from low (blue) to high (red) locations.
+pub fn heatmap(input: &str) -> SolveResult<String> {
+    Ok(parse(input)?.render_heatmap(|h| char::from_digit(*h, 10).unwrap(), |&h| h as f64))
 }
 
-fn flood_fill<'a>(matrix: &'a [Vec<u32>], p: &'a Point, basin: &'a mut Vec<Point>) {
-    surounding_points(matrix, p)
-        .iter()
-        .filter(|x| x.val != 9 && x.val > p.val)
-        .for_each(|x| {
-            flood_fill(matrix, x, basin);
-        });
-
-    if !basin.contains(p) {
-        basin.push(*p);
-    }
+pub fn try_part_one(input: &str) -> SolveResult<u32> {
+    let matrix = parse(input)?;
+    Ok(get_minimums(&matrix).iter().map(|p| p.val + 1).sum())
 }
 
-pub fn part_two(input: &str) -> usize {
-    let matrix = parse(input);
+pub fn try_part_two(input: &str) -> SolveResult<usize> {
+    let grid = Grid::from_rows(parse(input)?);
 
-    let mut basins = get_minimums(&matrix)
+    // every location that is not a `9` is part of exactly one basin.
+    let mut basins: Vec<usize> = grid
+        .label_regions(Connectivity::Four, |x| *x != 9)
+        .regions
         .iter()
-        .map(|p| {
-            let mut basin: Vec<Point> = Vec::new();
-            flood_fill(&matrix, p, &mut basin);
-            basin.len()
-        })
-        .collect::<Vec<usize>>();
+        .map(|r| r.size)
+        .collect();
 
     let len = basins.len();
+    if len < 3 {
+        return Err(SolveError::no_solution(format!(
+            "found {} basins, need at least 3",
+            len
+        )));
+    }
+
     basins.sort_unstable();
 
-    basins[len - 1] * basins[len - 2] * basins[len - 3]
+    Ok(basins[len - 1] * basins[len - 2] * basins[len - 3])
 }
 
 #[cfg(test)]
@@ -117,15 +113,25 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 9);
-        assert_eq!(part_one(&input), 15);
+        assert_eq!(try_part_one(&input), Ok(15));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
+        let input = read_file("examples", 9);
+        assert_eq!(try_part_two(&input), Ok(1134));
+    }
+
+    #[test]
+    fn test_heatmap() {
+        use crate::read_file;
         let input = read_file("examples", 9);
-        assert_eq!(part_two(&input), 1134);
+        let heatmap = heatmap(&input).unwrap();
+        assert_eq!(heatmap.lines().count(), input.lines().count());
+        // heights range from 0 to 9, the first location is a 2.
+        assert!(heatmap.starts_with("\x1b[38;5;39m2\x1b[38;5;33m1\x1b[38;5;196m9"));
     }
 }
@@ -1,10 +1,12 @@
+use crate::error::{SolveError, SolveResult};
 use crate::helpers::math::median;
+use crate::helpers::parse::{parse_lines, ParseResult};
 
 /// tracks open tokens (e.g. `(`) in sequence of occurence.
 type CharacterStack = Vec<char>;
 
 /// error thrown if parser fails to parse a line.
-struct ParsingError {
+pub(crate) struct ParsingError {
     token: char,
 }
 
@@ -26,7 +28,7 @@ fn opener(c: char) -> Option<char> {
 /// if the closing char can be used to close the pair, continue processing the line.
 /// if it does not match, throw a `ParsingError` referencing the offending token.
 /// once the line completes parsing without errors, return the rest of the stack.
-fn parse(line: &str) -> ParsingResult {
+pub(crate) fn parse(line: &str) -> ParsingResult {
     let mut stack: CharacterStack = Vec::new();
     let mut offending_token: Option<char> = None;
 
@@ -58,9 +60,19 @@ fn parse(line: &str) -> ParsingResult {
     }
 }
 
-pub fn part_one(input: &str) -> u32 {
-    input
-        .lines()
+/// check that every line consists of brackets only.
+fn validate(input: &str) -> ParseResult<Vec<&str>> {
+    parse_lines(input, |p| {
+        if p.is_done() {
+            return Ok("");
+        }
+        p.take_while("bracket", |c| "()[]{}<>".contains(c))
+    })
+}
+
+pub fn try_part_one(input: &str) -> SolveResult<u32> {
+    Ok(validate(input)?
+        .into_iter()
         .map(|l| match parse(l) {
             Ok(_) => 0,
             Err(err) => match err.token {
@@ -71,32 +83,33 @@ pub fn part_one(input: &str) -> u32 {
                 _ => 0,
             },
         })
-        .sum()
+        .sum())
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let mut scores: Vec<u64> = input
-        .lines()
-        .filter_map(|l| match parse(l) {
-            Ok(stack) => {
-                let score = stack.iter().rev().fold(0, |acc, char| {
-                    acc * 5
-                        + match char {
-                            '(' => 1,
-                            '[' => 2,
-                            '{' => 3,
-                            '<' => 4,
-                            _ => 0,
-                        }
-                });
-
-                Some(score)
-            }
-            Err(_) => None,
+pub fn try_part_two(input: &str) -> SolveResult<u64> {
+    let mut scores: Vec<u64> = validate(input)?
+        .into_iter()
+        .filter_map(|l| parse(l).ok())
+        .map(|stack| {
+            stack.iter().rev().try_fold(0u64, |acc, char| {
+                let value = match char {
+                    '(' => 1,
+                    '[' => 2,
+                    '{' => 3,
+                    '<' => 4,
+                    _ => 0,
+                };
+                acc.checked_mul(5)?.checked_add(value)
+            })
         })
-        .collect();
+        .collect::<Option<_>>()
+        .ok_or_else(|| SolveError::invalid("completion score does not fit into u64"))?;
+
+    if scores.is_empty() {
+        return Err(SolveError::no_solution("no incomplete lines"));
+    }
 
-    median(&mut scores)
+    Ok(median(&mut scores))
 }
 
 #[cfg(test)]
@@ -105,15 +118,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 10);
-        assert_eq!(part_one(&input), 26397);
+        assert_eq!(try_part_one(&input), Ok(26397));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 10);
-        assert_eq!(part_two(&input), 288957);
+        assert_eq!(try_part_two(&input), Ok(288957));
     }
 }
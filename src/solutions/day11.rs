@@ -1,5 +1,10 @@
+use crate::error::{SolveError, SolveResult};
 use crate::helpers::grid::Point;
+use crate::helpers::parse::parse_rows;
+use crate::helpers::render::Render;
+use crate::helpers::trace::{notify, Observer};
 use std::collections::HashSet;
+use std::fmt::{self, Display};
 
 static OCTOPUS_ROWS: usize = 10;
 static OCTOPUS_COLS: usize = 10;
@@ -7,19 +12,37 @@ static OCTOPUS_COLS: usize = 10;
 type Grid = [Line; 10];
 type Line = [u32; 10];
 
-fn parse(input: &str) -> Grid {
-    input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| c.to_digit(10).unwrap())
-                .collect::<Vec<u32>>()
-                .try_into()
-                .unwrap()
-        })
-        .collect::<Vec<Line>>()
-        .try_into()
-        .unwrap()
+fn parse(input: &str) -> SolveResult<Grid> {
+    let rows = parse_rows(input, "digit", |c| c.to_digit(10))?;
+
+    if rows.len() != OCTOPUS_ROWS || rows[0].len() != OCTOPUS_COLS {
+        return Err(SolveError::invalid(format!(
+            "expected a {}x{} grid",
+            OCTOPUS_COLS, OCTOPUS_ROWS
+        )));
+    }
+
+    Ok(std::array::from_fn(|y| std::array::from_fn(|x| rows[y][x])))
+}
+
+/// The octopuses after a step, passed to observers.
+pub struct Octopuses<'a> {
+    pub step: usize,
+    /// number of octopuses that flashed during this step.
+    pub flashes: u32,
+    grid: &'a Grid,
+}
+
+impl Display for Octopuses<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rendered = self.grid.render(|x| char::from_digit(*x, 10).unwrap());
+        write!(f, "{}\n{} flashes", rendered, self.flashes)
+    }
+}
+
+/// Render the energy levels of the octopuses as a terminal heat map, from low (blue) to high (red).
+pub fn heatmap(input: &str) -> SolveResult<String> {
+    Ok(parse(input)?.render_heatmap(|x| char::from_digit(*x, 10).unwrap(), |&x| x as f64))
 }
 
 fn process_step(grid: &mut Grid, all_points: &[Point]) -> u32 {
@@ -70,36 +93,77 @@ fn all_points() -> Vec<Point> {
     points
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let mut grid = parse(input);
+/// like `try_part_one`, but `observer` sees the octopuses after every step.
+pub fn observe_part_one(
+    input: &str,
+    observer: &mut impl for<'a> Observer<Octopuses<'a>>,
+) -> SolveResult<u32> {
+    let mut grid = parse(input)?;
     // optimization: keep a reference of all points in the grid to avoid recomputing this constantly.
     let points = all_points();
 
     let mut flash_count: u32 = 0;
-    for _ in 0..100 {
-        flash_count += process_step(&mut grid, &points);
+    for step in 1..=100 {
+        let flashes = process_step(&mut grid, &points);
+        flash_count += flashes;
+
+        let grid = &grid;
+        notify(
+            observer,
+            &Octopuses {
+                step,
+                flashes,
+                grid,
+            },
+        )?;
     }
 
-    flash_count
+    Ok(flash_count)
+}
+
+pub fn try_part_one(input: &str) -> SolveResult<u32> {
+    observe_part_one(input, &mut ())
 }
 
-pub fn part_two(input: &str) -> usize {
-    let mut grid = parse(input);
+/// like `try_part_two`, but `observer` sees the octopuses after every step.
+pub fn observe_part_two(
+    input: &str,
+    observer: &mut impl for<'a> Observer<Octopuses<'a>>,
+) -> SolveResult<usize> {
+    let mut grid = parse(input)?;
     // optimization: keep a reference of all points in the grid to avoid recomputing this constantly.
     let points = all_points();
+    // the grid only has finitely many states. once one repeats, the octopuses are stuck in a cycle.
+    let mut seen: HashSet<Grid> = HashSet::new();
 
     let mut index: usize = 0;
-    let mut all_flashed = false;
 
-    while !all_flashed {
-        if process_step(&mut grid, &points) == (OCTOPUS_COLS * OCTOPUS_ROWS) as u32 {
-            all_flashed = true
-        } else {
-            index += 1;
+    while seen.insert(grid) {
+        index += 1;
+        let flashes = process_step(&mut grid, &points);
+
+        let (step, grid) = (index, &grid);
+        notify(
+            observer,
+            &Octopuses {
+                step,
+                flashes,
+                grid,
+            },
+        )?;
+
+        if flashes == (OCTOPUS_COLS * OCTOPUS_ROWS) as u32 {
+            return Ok(index);
         }
     }
 
-    index + 1
+    Err(SolveError::no_solution(
+        "octopuses never flash simultaneously",
+    ))
+}
+
+pub fn try_part_two(input: &str) -> SolveResult<usize> {
+    observe_part_two(input, &mut ())
 }
 
 #[cfg(test)]
@@ -108,15 +172,77 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 11);
-        assert_eq!(part_one(&input), 1656);
+        assert_eq!(try_part_one(&input), Ok(1656));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
+        let input = read_file("examples", 11);
+        assert_eq!(try_part_two(&input), Ok(195));
+    }
+
+    #[test]
+    fn test_snapshot() {
+        use crate::helpers::snapshot::assert_snapshot;
+        use crate::read_file;
+        use std::fmt::Write;
+
+        let mut grid = parse(&read_file("examples", 11)).unwrap();
+        let points = all_points();
+        let mut states = String::new();
+
+        for step in 1..=100 {
+            let flashes = process_step(&mut grid, &points);
+            if step <= 10 || step % 10 == 0 {
+                let rendered = grid.render(|x| char::from_digit(*x, 10).unwrap());
+                write!(
+                    states,
+                    "after step {} ({} flashes):\n{}\n\n",
+                    step, flashes, rendered
+                )
+                .unwrap();
+            }
+        }
+
+        assert_snapshot("day11", &states);
+    }
+
+    #[test]
+    fn test_observe() {
+        use crate::read_file;
+        use std::ops::ControlFlow;
+
+        let input = read_file("examples", 11);
+        let mut flashes = Vec::new();
+        let result = observe_part_one(&input, &mut |octopuses: &Octopuses| {
+            flashes.push(octopuses.flashes);
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(result, Ok(1656));
+        assert_eq!(flashes.len(), 100);
+        assert_eq!(flashes[..3], [0, 35, 45]);
+        assert_eq!(flashes.iter().sum::<u32>(), 1656);
+
+        let mut stop_at_ten = |octopuses: &Octopuses| match octopuses.step {
+            10 => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        };
+        assert_eq!(
+            observe_part_one(&input, &mut stop_at_ten),
+            Err(SolveError::Stopped)
+        );
+    }
+
+    #[test]
+    fn test_heatmap() {
+        use crate::read_file;
         let input = read_file("examples", 11);
-        assert_eq!(part_two(&input), 195);
+        let heatmap = heatmap(&input).unwrap();
+        assert_eq!(heatmap.lines().count(), input.lines().count());
+        assert!(heatmap.lines().all(|line| line.ends_with("\x1b[0m")));
     }
 }
@@ -1,3 +1,5 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::{parse_lines, ParseResult, Parser};
 use std::collections::{HashMap, HashSet};
 
 static START: &str = "start";
@@ -25,22 +27,45 @@ impl Graph<'_> {
     }
 }
 
-fn parse(input: &str) -> Graph {
-    let mut graph = Graph::new();
+fn parse_cave<'a>(p: &mut Parser<'a>) -> ParseResult<&'a str> {
+    p.take_while("cave name", |c| c.is_ascii_alphabetic())
+}
 
-    input.lines().for_each(|l| {
-        let mut node_pair = l.split('-').map(|id| (id, id.to_uppercase() == id));
+fn parse(input: &str) -> SolveResult<Graph<'_>> {
+    let mut graph = Graph::new();
 
-        let (from, from_type) = node_pair.next().unwrap();
-        let (to, to_type) = node_pair.next().unwrap();
+    let edges = parse_lines(input, |p| {
+        let from = parse_cave(p)?;
+        p.tag("-")?;
+        let to = parse_cave(p)?;
+        Ok((from, to))
+    })?;
+
+    for (from, to) in edges {
+        let from_type = from.to_uppercase() == from;
+        let to_type = to.to_uppercase() == to;
+
+        // two connected big caves could be visited back and forth indefinitely.
+        if from_type && to_type {
+            return Err(SolveError::invalid(format!(
+                "big caves `{}` and `{}` are connected, there are infinitely many paths",
+                from, to
+            )));
+        }
 
         graph.nodes.insert(from, from_type);
         graph.nodes.insert(to, to_type);
         graph.edges.entry(from).or_default().insert(to);
         graph.edges.entry(to).or_default().insert(from);
-    });
+    }
+
+    for cave in [START, END] {
+        if !graph.nodes.contains_key(cave) {
+            return Err(SolveError::invalid(format!("missing cave `{}`", cave)));
+        }
+    }
 
-    graph
+    Ok(graph)
 }
 
 fn search(graph: &Graph, seen: &HashSet<&str>, id: &str, small_room_counter: u8) -> u32 {
@@ -77,16 +102,16 @@ fn search(graph: &Graph, seen: &HashSet<&str>, id: &str, small_room_counter: u8)
     result
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let graph = parse(input);
+pub fn try_part_one(input: &str) -> SolveResult<u32> {
+    let graph = parse(input)?;
     let seen: HashSet<&str> = HashSet::new();
-    search(&graph, &seen, START, 0)
+    Ok(search(&graph, &seen, START, 0))
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let graph = parse(input);
+pub fn try_part_two(input: &str) -> SolveResult<u32> {
+    let graph = parse(input)?;
     let seen: HashSet<&str> = HashSet::new();
-    search(&graph, &seen, START, 1)
+    Ok(search(&graph, &seen, START, 1))
 }
 
 #[cfg(test)]
@@ -95,15 +120,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 12);
-        assert_eq!(part_one(&input), 226);
+        assert_eq!(try_part_one(&input), Ok(226));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 12);
-        assert_eq!(part_two(&input), 3509);
+        assert_eq!(try_part_two(&input), Ok(3509));
     }
 }
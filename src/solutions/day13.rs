@@ -1,12 +1,11 @@
-#![allow(clippy::needless_range_loop)]
-use crate::helpers::grid::Point;
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::grid::{Axis, Grid, Point};
+use crate::helpers::parse::{sections, ParseResult, Parser};
+use crate::helpers::render::Render;
 use std::cmp::max;
 
 type Points = Vec<Point>;
 
-type Line = Vec<bool>;
-type Grid = Vec<Line>;
-
 #[derive(Debug)]
 enum Instruction {
     X(usize),
@@ -14,127 +13,109 @@ enum Instruction {
 }
 type Instructions = Vec<Instruction>;
 
-fn parse(input: &str) -> (Grid, Instructions) {
-    let mut points: Points = Vec::new();
-    let mut instructions: Instructions = Vec::new();
+fn parse(input: &str) -> SolveResult<(Grid<bool>, Instructions)> {
+    let [points, instructions] = sections(input)?;
+    let points: Points = points.parse_lines(parse_point)?;
+    let instructions: Instructions = instructions.parse_lines(parse_instruction)?;
 
     let mut width: usize = 0;
     let mut height: usize = 0;
 
-    input.lines().for_each(|l| {
-        // line is an instruction.
-        if l.starts_with('f') {
-            let instruction = parse_instruction(l);
-
-            // infer grid size from first instructions.
-            // looking at max. point size might fail if last lines or columns are empty.
-            if width == 0 || height == 0 {
-                match instruction {
-                    Instruction::X(x) => width = max(x * 2 + 1, width),
-                    Instruction::Y(y) => height = max(y * 2 + 1, height),
-                }
+    // infer grid size from first instructions.
+    // looking at max. point size might fail if last lines or columns are empty.
+    for instruction in &instructions {
+        if width == 0 || height == 0 {
+            match instruction {
+                Instruction::X(x) => width = max(x * 2 + 1, width),
+                Instruction::Y(y) => height = max(y * 2 + 1, height),
             }
-
-            instructions.push(instruction);
-        // line is a point.
-        } else if !l.is_empty() {
-            points.push(parse_point(l));
         }
-    });
-
-    (make_grid(&points, width, height), instructions)
-}
-
-fn parse_point(l: &str) -> Point {
-    let mut coords = l.split(',');
-    let x: usize = coords.next().unwrap().parse().unwrap();
-    let y: usize = coords.next().unwrap().parse().unwrap();
-    Point(x, y)
-}
-
-fn parse_instruction(l: &str) -> Instruction {
-    let mut instr = l.split(' ').last().unwrap().split('=');
-    let axis = instr.next().unwrap();
-    let amount: usize = instr.next().unwrap().parse().unwrap();
-
-    if axis == "x" {
-        Instruction::X(amount)
-    } else {
-        Instruction::Y(amount)
     }
-}
 
-fn make_grid(points: &[Point], width: usize, height: usize) -> Grid {
-    let mut grid: Grid = vec![vec![false; width]; height];
+    // without a fold along an axis, the grid only needs to fit the points.
+    if width == 0 {
+        width = points.iter().map(|p| p.0 + 1).max().unwrap_or(0);
+    }
+    if height == 0 {
+        height = points.iter().map(|p| p.1 + 1).max().unwrap_or(0);
+    }
 
-    for Point(x, y) in points {
-        grid[*y][*x] = true;
+    if let Some(Point(x, y)) = points
+        .iter()
+        .find(|Point(x, y)| *x >= width || *y >= height)
+    {
+        return Err(SolveError::invalid(format!(
+            "point {},{} is outside of the {}x{} paper",
+            x, y, width, height
+        )));
     }
 
-    grid
+    Ok((make_grid(&points, width, height), instructions))
 }
 
-fn fold_y(grid: &[Line], fold_at: usize, width: usize, height: usize) -> Grid {
-    let mut points: Points = Vec::new();
+fn parse_point(p: &mut Parser) -> ParseResult<Point> {
+    let x = p.integer()?;
+    p.tag(",")?;
+    let y = p.integer()?;
+    Ok(Point(x, y))
+}
 
-    for y in 0..fold_at {
-        for x in 0..width {
-            if grid[y][x] || grid[height - y - 1][x] {
-                points.push(Point(x, y));
-            }
-        }
-    }
+fn parse_instruction(p: &mut Parser) -> ParseResult<Instruction> {
+    p.tag("fold along ")?;
 
-    make_grid(&points, width, fold_at)
+    if p.try_tag("x=") {
+        Ok(Instruction::X(p.integer()?))
+    } else if p.try_tag("y=") {
+        Ok(Instruction::Y(p.integer()?))
+    } else {
+        Err(p.error("`x=` or `y=`"))
+    }
 }
 
-fn fold_x(grid: &[Line], fold_at: usize, width: usize, height: usize) -> Grid {
-    let mut points: Points = Vec::new();
+fn make_grid(points: &[Point], width: usize, height: usize) -> Grid<bool> {
+    let mut grid = Grid::filled(width, height, false);
 
-    for y in 0..height {
-        for x in 0..fold_at {
-            if grid[y][x] || grid[y][width - x - 1] {
-                points.push(Point(x, y));
-            }
-        }
+    for p in points {
+        grid[*p] = true;
     }
 
-    make_grid(&points, fold_at, height)
+    grid
 }
 
-fn fold(grid: &[Line], instruction: &Instruction) -> Grid {
-    let height = grid.len();
-    let width = grid[0].len();
-
-    match instruction {
-        Instruction::X(fold_at) => fold_x(grid, *fold_at, width, height),
-        Instruction::Y(fold_at) => fold_y(grid, *fold_at, width, height),
+fn fold(grid: &Grid<bool>, instruction: &Instruction) -> SolveResult<Grid<bool>> {
+    let (axis, fold_at, len) = match instruction {
+        Instruction::X(fold_at) => (Axis::X, *fold_at, grid.width()),
+        Instruction::Y(fold_at) => (Axis::Y, *fold_at, grid.height()),
+    };
+
+    if fold_at >= len {
+        return Err(SolveError::invalid(format!(
+            "{:?} is outside of the paper",
+            instruction
+        )));
     }
-}
 
-fn count_grid(grid: &[Line]) -> u32 {
-    grid.iter().flatten().filter(|x| **x).count() as u32
+    Ok(grid.fold(axis, fold_at, |a, b| *a || *b))
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let (grid, instructions) = parse(input);
-    count_grid(&fold(&grid, &instructions[0]))
+fn count_grid(grid: &Grid<bool>) -> u32 {
+    grid.cells().filter(|x| **x).count() as u32
 }
 
-fn print_grid(grid: &[Line]) {
-    for line in grid {
-        let chars: String = line.iter().map(|x| if *x { '#' } else { '.' }).collect();
-        println!("{}", chars);
-    }
+pub fn try_part_one(input: &str) -> SolveResult<u32> {
+    let (grid, instructions) = parse(input)?;
+    Ok(count_grid(&fold(&grid, &instructions[0])?))
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let (grid, instructions) = parse(input);
+pub fn try_part_two(input: &str) -> SolveResult<u32> {
+    let (grid, instructions) = parse(input)?;
 
-    let code = instructions.iter().fold(grid, |acc, curr| fold(&acc, curr));
+    let code = instructions
+        .iter()
+        .try_fold(grid, |acc, curr| fold(&acc, curr))?;
 
-    print_grid(&code);
-    count_grid(&code)
+    println!("{}", code.render(|&x| if x { '#' } else { '.' }));
+    Ok(count_grid(&code))
 }
 
 #[cfg(test)]
@@ -143,15 +124,76 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 13);
-        assert_eq!(part_one(&input), 17);
+        assert_eq!(try_part_one(&input), Ok(17));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 13);
-        assert_eq!(part_two(&input), 16);
+        assert_eq!(try_part_two(&input), Ok(16));
+    }
+
+    #[test]
+    fn test_fold_properties() {
+        use crate::helpers::prop::check;
+
+        // folding an already folded and unfolded paper again gives the same paper back.
+        check::<(Vec<bool>, u8, bool)>(|(cells, at, along_x)| {
+            let at = *at as usize % 4 + 1;
+            let len = at * 2 + 1;
+            let rows: Vec<Vec<bool>> = cells.chunks_exact(len).map(|c| c.to_vec()).collect();
+            if rows.is_empty() {
+                return true;
+            }
+
+            let (grid, instruction) = if *along_x {
+                (Grid::from_rows(rows), Instruction::X(at))
+            } else {
+                (Grid::from_rows(rows).transpose(), Instruction::Y(at))
+            };
+
+            let folded = fold(&grid, &instruction).unwrap();
+            let unfolded = Grid::from_fn(grid.width(), grid.height(), |Point(x, y)| {
+                let i = if *along_x { x } else { y };
+                let mirrored = match i {
+                    i if i < at => i,
+                    i if i > at => 2 * at - i,
+                    _ => return false,
+                };
+                if *along_x {
+                    folded[Point(mirrored, y)]
+                } else {
+                    folded[Point(x, mirrored)]
+                }
+            });
+
+            fold(&unfolded, &instruction).unwrap() == folded
+                && count_grid(&folded) <= count_grid(&grid)
+        });
+    }
+
+    #[test]
+    fn test_snapshot() {
+        use crate::helpers::snapshot::assert_snapshot;
+        use crate::read_file;
+        use std::fmt::Write;
+
+        let (mut grid, instructions) = parse(&read_file("examples", 13)).unwrap();
+        let render = |grid: &Grid<bool>| grid.render(|&x| if x { '#' } else { '.' });
+        let mut states = format!("before folding:\n{}\n\n", render(&grid));
+
+        for instruction in &instructions {
+            grid = fold(&grid, instruction).unwrap();
+            let line = match instruction {
+                Instruction::X(x) => format!("x={}", x),
+                Instruction::Y(y) => format!("y={}", y),
+            };
+            write!(states, "after fold along {}:\n{}\n\n", line, render(&grid)).unwrap();
+        }
+
+        assert_snapshot("day13", &states);
     }
 }
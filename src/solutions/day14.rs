@@ -1,3 +1,6 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::matrix::DynMatrix;
+use crate::helpers::parse::{parse_line, sections, ParseResult, Parser};
 use itertools::{Itertools, MinMaxResult};
 use std::collections::HashMap;
 
@@ -9,91 +12,171 @@ type Rules = HashMap<Pair, char>;
 /// example: `{ (N, N): 1, (N, C): 1, (C, B): 1 }` for the example polymer.
 /// A polymerization step creates a new set of pairs by processing `rules` for every pair currently in the polymer.
 /// example: `{ (N, N): 3 }` produces `{ (N, C): 2, (C, N): 2 }`.
-/// Looking at `pairs` is not enough to derive character counts, so we need a `chars` map to count these as well.
-/// whenever a character is added to a pair, we increment a running counter for that character.
-/// example: `{ (N, N): 3 }` would increment `C` by 2.
+/// Since pairs overlap by one character, every character is the first character of a pair except for the last one.
+/// The last character of a polymer never changes, so we can derive character counts from `pairs` and `last`.
 #[derive(Clone)]
 struct Polymer {
     pairs: HashMap<Pair, u64>,
-    characters: HashMap<char, u64>,
+    last: char,
 }
 
 impl Polymer {
-    fn new() -> Self {
+    fn from_string(line: &str) -> Self {
+        let mut pairs = HashMap::new();
+
+        line.chars().tuple_windows().for_each(|(a, b)| {
+            *pairs.entry((a, b)).or_default() += 1;
+        });
+
         Polymer {
-            pairs: HashMap::new(),
-            characters: HashMap::new(),
+            pairs,
+            // the template is never empty, see `parse`.
+            last: line.chars().last().unwrap(),
         }
     }
 
-    fn from_string(line: &str) -> Self {
-        let mut polymer = Polymer::new();
-        let chars = line.chars();
+    /// A polymerization step is a linear transformation of pair counts.
+    /// We describe it as a matrix that maps every pair to the pairs it produces.
+    /// Expanding `times` steps then is a single matrix power, which takes `log(times)` matrix products.
+    fn expand_times(&mut self, times: u64, rules: &Rules) -> SolveResult<&Self> {
+        let elements: Vec<char> = rules
+            .iter()
+            .flat_map(|((a, b), c)| [*a, *b, *c])
+            .chain(self.pairs.keys().flat_map(|(a, b)| [*a, *b]))
+            .unique()
+            .sorted_unstable()
+            .collect();
+
+        let pair_count = elements.len() * elements.len();
+        let index = |(a, b): Pair| {
+            let position = |c| elements.iter().position(|x| *x == c).unwrap();
+            position(a) * elements.len() + position(b)
+        };
+
+        let mut transition = DynMatrix::zero(pair_count, pair_count);
+
+        for pair in elements.iter().cartesian_product(elements.iter()) {
+            let pair = (*pair.0, *pair.1);
+            let from = index(pair);
+
+            match rules.get(&pair) {
+                Some(to_add) => {
+                    transition[(index((pair.0, *to_add)), from)] += 1;
+                    transition[(index((*to_add, pair.1)), from)] += 1;
+                }
+                // pairs without a matching rule are left untouched.
+                None => transition[(from, from)] = 1,
+            }
+        }
 
-        chars.clone().for_each(|c| {
-            *polymer.characters.entry(c).or_default() += 1;
-        });
+        let mut counts = vec![0; pair_count];
+        for (pair, count) in &self.pairs {
+            counts[index(*pair)] = *count;
+        }
 
-        chars.clone().tuple_windows().for_each(|(a, b)| {
-            *polymer.pairs.entry((a, b)).or_default() += 1;
-        });
+        let counts = transition
+            .checked_pow(times)
+            .and_then(|matrix| matrix.checked_apply(&counts))
+            .ok_or_else(|| SolveError::invalid("pair counts do not fit into u64"))?;
 
-        polymer
+        self.pairs = elements
+            .iter()
+            .cartesian_product(elements.iter())
+            .map(|(a, b)| ((*a, *b), counts[index((*a, *b))]))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+
+        Ok(self)
     }
 
-    fn expand(&mut self, rules: &Rules) {
-        let pairs = self.pairs.clone();
-        self.pairs.clear();
+    fn characters(&self) -> SolveResult<HashMap<char, u64>> {
+        let mut characters = HashMap::from([(self.last, 1u64)]);
 
-        for (pair, count) in pairs {
-            let to_add = rules.get(&pair).unwrap().to_owned();
-            *self.pairs.entry((pair.0, to_add)).or_default() += count;
-            *self.pairs.entry((to_add, pair.1)).or_default() += count;
-            *self.characters.entry(to_add).or_default() += count;
+        for ((a, _), count) in &self.pairs {
+            let total = characters.entry(*a).or_default();
+            *total = total
+                .checked_add(*count)
+                .ok_or_else(|| SolveError::invalid("character counts do not fit into u64"))?;
         }
-    }
 
-    fn expand_times(&mut self, times: u8, rules: &Rules) -> &Self {
-        for _ in 0..times {
-            self.expand(rules);
-        }
-        self
+        Ok(characters)
     }
 
-    fn delta(&self) -> u64 {
-        match self.characters.values().minmax() {
+    fn delta(&self) -> SolveResult<u64> {
+        Ok(match self.characters()?.values().minmax() {
             MinMaxResult::MinMax(a, b) => b - a,
-            _ => unreachable!(),
+            // a polymer made up of a single element.
+            _ => 0,
+        })
+    }
+}
+
+fn parse_element(p: &mut Parser) -> ParseResult<char> {
+    match p.rest().chars().next() {
+        Some(c) if c.is_ascii_uppercase() => {
+            p.tag(&c.to_string())?;
+            Ok(c)
         }
+        _ => Err(p.error("element")),
     }
 }
 
-fn parse(input: &str) -> (Polymer, Rules) {
-    let mut lines = input.lines();
+fn parse(input: &str) -> SolveResult<(Polymer, Rules)> {
+    let [template, rules] = sections(input)?;
 
-    let polymer = Polymer::from_string(lines.next().unwrap());
+    let template = parse_line(template.text, template.first_line, |p| {
+        p.take_while("element", |c| c.is_ascii_uppercase())
+    })?;
 
-    let rules = lines.fold(HashMap::new(), |mut acc, l| {
-        if !l.is_empty() {
-            let parts: Vec<char> = l.replace(" -> ", "").chars().collect();
-            let pair = (parts[0], parts[1]);
-            acc.insert(pair, parts[2]);
-        }
+    let rules = rules.parse_lines(|p| {
+        let pair = (parse_element(p)?, parse_element(p)?);
+        p.tag(" -> ")?;
+        Ok((pair, parse_element(p)?))
+    })?;
 
-        acc
-    });
+    Ok((Polymer::from_string(template), rules.into_iter().collect()))
+}
 
-    (polymer, rules)
+pub fn try_part_one(input: &str) -> SolveResult<u64> {
+    let (mut polymer, rules) = parse(input)?;
+    polymer.expand_times(10, &rules)?.delta()
 }
 
-pub fn part_one(input: &str) -> u64 {
-    let (mut polymer, rules) = parse(input);
-    polymer.expand_times(10, &rules).delta()
+pub fn try_part_two(input: &str) -> SolveResult<u64> {
+    let (mut polymer, rules) = parse(input)?;
+    polymer.expand_times(40, &rules)?.delta()
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let (mut polymer, rules) = parse(input);
-    polymer.expand_times(40, &rules).delta()
+/// expands the polymer as a string, doubling its length every step.
+#[cfg(test)]
+mod naive {
+    use super::Rules;
+    use std::collections::HashMap;
+
+    pub fn expand(template: &str, rules: &Rules, steps: u64) -> String {
+        let mut polymer: Vec<char> = template.chars().collect();
+
+        for _ in 0..steps {
+            let mut next = vec![polymer[0]];
+            for pair in polymer.windows(2) {
+                if let Some(c) = rules.get(&(pair[0], pair[1])) {
+                    next.push(*c);
+                }
+                next.push(pair[1]);
+            }
+            polymer = next;
+        }
+
+        polymer.into_iter().collect()
+    }
+
+    pub fn characters(template: &str, rules: &Rules, steps: u64) -> HashMap<char, u64> {
+        let mut characters = HashMap::new();
+        for c in expand(template, rules, steps).chars() {
+            *characters.entry(c).or_default() += 1;
+        }
+        characters
+    }
 }
 
 #[cfg(test)]
@@ -102,15 +185,68 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 14);
-        assert_eq!(part_one(&input), 1588);
+        assert_eq!(try_part_one(&input), Ok(1588));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 14);
-        assert_eq!(part_two(&input), 2188189693529);
+        assert_eq!(try_part_two(&input), Ok(2188189693529));
+    }
+
+    #[test]
+    fn test_naive() {
+        use crate::generate;
+        use crate::helpers::rng::Rng;
+
+        for seed in 0..10 {
+            // covers 2 to 14 elements.
+            let input = generate::day14(&mut Rng::new(seed), 2 + seed as usize * 11 % 13);
+            let (polymer, rules) = parse(&input).unwrap();
+            let template = input.lines().next().unwrap();
+
+            for steps in 0..=8 {
+                let mut expanded = polymer.clone();
+                expanded.expand_times(steps, &rules).unwrap();
+                assert_eq!(
+                    expanded.characters(),
+                    Ok(naive::characters(template, &rules, steps))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_snapshot() {
+        use crate::helpers::snapshot::assert_snapshot;
+        use crate::read_file;
+        use std::fmt::Write;
+
+        let input = read_file("examples", 14);
+        let (mut polymer, rules) = parse(&input).unwrap();
+        let template = input.lines().next().unwrap();
+        let mut states = format!("template: {}\n\n", template);
+
+        for step in 1..=10 {
+            polymer.expand_times(1, &rules).unwrap();
+            let pairs = polymer
+                .pairs
+                .iter()
+                .sorted()
+                .map(|((a, b), count)| format!("{}{}={}", a, b, count))
+                .join(" ");
+
+            writeln!(states, "after step {}:", step).unwrap();
+            // the string doubles in length every step, only early polymers are readable.
+            if step <= 4 {
+                writeln!(states, "polymer: {}", naive::expand(template, &rules, step)).unwrap();
+            }
+            write!(states, "pairs: {}\n\n", pairs).unwrap();
+        }
+
+        assert_snapshot("day14", &states);
     }
 }
@@ -5,7 +5,7 @@
 /// [Wikipedia](https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm) |
 /// [Introduction to the A* Algorithm](https://www.redblobgames.com/pathfinding/a-star/introduction.html).
 mod shortest_path {
-    use crate::helpers::grid::Point;
+    use crate::helpers::grid::{Grid, Point};
     use std::cmp::Ordering;
     use std::collections::BinaryHeap;
 
@@ -34,9 +34,9 @@ mod shortest_path {
         }
     }
 
-    pub fn shortest_path(grid: &[Vec<u32>]) -> Option<usize> {
-        let height = grid.len();
-        let width = grid[0].len();
+    pub fn shortest_path(grid: &Grid<u32>) -> Option<usize> {
+        let height = grid.height();
+        let width = grid.width();
 
         // dist[node] = current shortest distance from `start` to `node`.
         let mut dist: Vec<_> = (0..(height * width)).map(|_| usize::MAX).collect();
@@ -44,7 +44,7 @@ mod shortest_path {
         let mut frontier = BinaryHeap::new();
 
         let start_node = Point(0, 0).to_id(width);
-        let target_node = Point(height - 1, width - 1).to_id(width);
+        let target_node = Point(width - 1, height - 1).to_id(width);
 
         // initialize start with a zero cost.
         dist[start_node] = 0;
@@ -65,9 +65,9 @@ mod shortest_path {
             }
 
             // see if we can find a path with a lower cost than previous paths for any adjacent nodes.
-            for point in Point::from_id(position, width).neighbors(width - 1, height - 1, false) {
+            for point in grid.neighbors(Point::from_id(position, width), false) {
                 let next = State {
-                    cost: cost + grid[point.1][point.0] as usize,
+                    cost: cost + grid[point] as usize,
                     position: point.to_id(width),
                 };
 
@@ -84,48 +84,44 @@ mod shortest_path {
 }
 
 use self::shortest_path::shortest_path;
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::grid::Grid;
+use crate::helpers::parse::{parse_rows, ParseResult};
+use crate::helpers::render::Render;
+
+fn parse(input: &str) -> ParseResult<Grid<u32>> {
+    Ok(Grid::from_rows(parse_rows(input, "digit", |c| {
+        c.to_digit(10)
+    })?))
+}
 
-type Row = Vec<u32>;
-type Grid = Vec<Row>;
+fn lowest_risk(grid: &Grid<u32>) -> SolveResult<u32> {
+    shortest_path(grid)
+        .map(|cost| cost as u32)
+        .ok_or_else(|| SolveError::no_solution("bottom right can not be reached"))
+}
 
-fn parse(input: &str) -> Grid {
-    input
-        .lines()
-        .map(|l| l.chars().map(|c| c.to_digit(10).unwrap()).collect())
-        .collect()
+/// Render the risk levels of the cave as a terminal heat map, from low (blue) to high (red).
+pub fn heatmap(input: &str) -> SolveResult<String> {
+    Ok(parse(input)?.render_heatmap(|r| char::from_digit(*r, 10).unwrap(), |&r| r as f64))
 }
 
-pub fn part_one(input: &str) -> u32 {
-    shortest_path(&parse(input)).unwrap() as u32
+pub fn try_part_one(input: &str) -> SolveResult<u32> {
+    lowest_risk(&parse(input)?)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let grid = parse(input);
-
-    let height = grid.len();
-    let width = grid[0].len();
-
-    let expanded: Grid = (0..(5 * grid.len()))
-        .map(|y| {
-            (0..(5 * grid[0].len()))
-                .map(|x| {
-                    // increment grows by one with every horizontal *and* vertical tile.
-                    let x_increment = (x / width) as u32;
-                    let y_increment = (y / height) as u32;
-
-                    // each individual value can be derived from the original value and the current distance to it.
-                    let cost = grid[x % width][y % height] + x_increment + y_increment;
-                    if cost == 9 {
-                        cost
-                    } else {
-                        cost % 9
-                    }
-                })
-                .collect()
-        })
-        .collect();
-
-    shortest_path(&expanded).unwrap() as u32
+pub fn try_part_two(input: &str) -> SolveResult<u32> {
+    // risk grows by one with every horizontal *and* vertical tile and wraps around to `1` after `9`.
+    let expanded = parse(input)?.tile(5, 5, |risk, x, y| {
+        let cost = risk + (x + y) as u32;
+        if cost == 9 {
+            cost
+        } else {
+            cost % 9
+        }
+    });
+
+    lowest_risk(&expanded)
 }
 
 #[cfg(test)]
@@ -134,15 +130,25 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 15);
-        assert_eq!(part_one(&input), 40);
+        assert_eq!(try_part_one(&input), Ok(40));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
+        let input = read_file("examples", 15);
+        assert_eq!(try_part_two(&input), Ok(315));
+    }
+
+    #[test]
+    fn test_heatmap() {
+        use crate::read_file;
         let input = read_file("examples", 15);
-        assert_eq!(part_two(&input), 315);
+        let heatmap = heatmap(&input).unwrap();
+        assert_eq!(heatmap.lines().count(), input.lines().count());
+        // risk levels range from 1 to 9, the first location is a 1.
+        assert!(heatmap.starts_with("\x1b[38;5;21m1"));
     }
 }
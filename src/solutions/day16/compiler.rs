@@ -0,0 +1,277 @@
+use super::decoder::{Header, LengthType, Literal, Operator, Packet};
+use super::encoder::encode;
+use crate::error::SolveResult;
+use crate::helpers::parse::{parse_line, ParseResult, Parser};
+
+/// functions that can be called by name, with the type id of the operator they compile to.
+/// `gt`, `lt` and `eq` take exactly two operands, the others at least one.
+const FUNCTIONS: [(&str, u64); 7] = [
+    ("sum", 0),
+    ("product", 1),
+    ("min", 2),
+    ("max", 3),
+    ("gt", 5),
+    ("lt", 6),
+    ("eq", 7),
+];
+
+/// infix comparisons, `==` needs to be tried before any shorter tag.
+const COMPARISONS: [(&str, u64); 3] = [("==", 7), (">", 5), ("<", 6)];
+
+/// parentheses, of groups and calls alike, nest at most this deep. the parser recurses once per level.
+const MAX_NESTING: usize = 100;
+
+/// Compile an expression to a hexadecimal BITS transmission that `interpreter::interpret` evaluates to the
+/// expression's value.
+pub fn compile(expression: &str) -> SolveResult<String> {
+    encode(&parse(expression)?)
+}
+
+/// Parse an expression into a packet tree, every packet has version `0`.
+///
+/// Expressions are made up of unsigned integers, parentheses, `+`, `*` and the comparisons `<`, `>` and `==`,
+/// plus calls to `sum`, `product`, `min`, `max`, `gt`, `lt` and `eq`, e.g. `min(3, 7*2) + (5 > 2)`.
+/// `*` binds tighter than `+`, which binds tighter than comparisons. Comparisons can not be chained.
+/// Parentheses can be nested up to 100 levels deep.
+pub fn parse(expression: &str) -> SolveResult<Packet> {
+    parse_line(expression, 1, check_nesting)?;
+
+    Ok(parse_line(expression, 1, |p| {
+        let packet = comparison(p)?;
+        skip_spaces(p);
+        Ok(packet)
+    })?)
+}
+
+/// reject expressions that would make the parser recurse too deeply before parsing them.
+fn check_nesting(p: &mut Parser) -> ParseResult<()> {
+    let mut depth = 0;
+
+    loop {
+        let _ = p.take_while("parenthesis", |c| c != '(' && c != ')');
+
+        if p.rest().starts_with('(') {
+            if depth == MAX_NESTING {
+                return Err(p.error(format!(
+                    "parentheses nested at most {} levels deep",
+                    MAX_NESTING
+                )));
+            }
+            p.tag("(")?;
+            depth += 1;
+        } else if p.try_tag(")") {
+            // unbalanced parentheses are reported by the parser itself.
+            depth = depth.saturating_sub(1);
+        } else {
+            return Ok(());
+        }
+    }
+}
+
+fn skip_spaces(p: &mut Parser) {
+    let _ = p.take_while("space", |c| c == ' ');
+}
+
+/// consume `tag`, ignoring the spaces in front of it.
+fn try_token(p: &mut Parser, tag: &str) -> bool {
+    skip_spaces(p);
+    p.try_tag(tag)
+}
+
+fn token(p: &mut Parser, tag: &str) -> ParseResult<()> {
+    skip_spaces(p);
+    p.tag(tag)
+}
+
+fn literal(value: u64) -> Packet {
+    Packet::Literal(Literal {
+        header: Header {
+            version: 0,
+            type_id: 4,
+        },
+        value,
+    })
+}
+
+fn operator(type_id: u64, children: Vec<Packet>) -> Packet {
+    Packet::Operator(Operator {
+        header: Header {
+            version: 0,
+            type_id,
+        },
+        length_type: LengthType::PacketCount,
+        children,
+    })
+}
+
+fn comparison(p: &mut Parser) -> ParseResult<Packet> {
+    let left = sum(p)?;
+
+    for (tag, type_id) in COMPARISONS {
+        if try_token(p, tag) {
+            let right = sum(p)?;
+
+            skip_spaces(p);
+            if COMPARISONS.iter().any(|(tag, _)| p.rest().starts_with(tag)) {
+                return Err(p.error("end of comparison, comparisons can not be chained"));
+            }
+
+            return Ok(operator(type_id, vec![left, right]));
+        }
+    }
+
+    Ok(left)
+}
+
+/// parse a chain of `item`s joined by `tag` into a single operator, or just the item if there is no `tag`.
+fn chain(
+    p: &mut Parser,
+    tag: &str,
+    type_id: u64,
+    item: fn(&mut Parser) -> ParseResult<Packet>,
+) -> ParseResult<Packet> {
+    let mut children = vec![item(p)?];
+
+    while try_token(p, tag) {
+        children.push(item(p)?);
+    }
+
+    Ok(match children.len() {
+        1 => children.pop().unwrap(),
+        _ => operator(type_id, children),
+    })
+}
+
+fn sum(p: &mut Parser) -> ParseResult<Packet> {
+    chain(p, "+", 0, product)
+}
+
+fn product(p: &mut Parser) -> ParseResult<Packet> {
+    chain(p, "*", 1, atom)
+}
+
+fn atom(p: &mut Parser) -> ParseResult<Packet> {
+    skip_spaces(p);
+
+    if p.try_tag("(") {
+        let packet = comparison(p)?;
+        token(p, ")")?;
+        return Ok(packet);
+    }
+
+    if p.rest().starts_with(|c: char| c.is_ascii_digit()) {
+        return Ok(literal(p.integer()?));
+    }
+
+    let name = p
+        .word()
+        .map_err(|_| p.error("integer, `(` or function name"))?;
+    let type_id = FUNCTIONS
+        .iter()
+        .find(|(function, _)| *function == name)
+        .map(|&(_, type_id)| type_id)
+        .ok_or_else(|| {
+            let names: Vec<&str> = FUNCTIONS.iter().map(|(function, _)| *function).collect();
+            p.error(format!("one of {}", names.join(", ")))
+        })?;
+
+    token(p, "(")?;
+    let arguments = p.separated(",", comparison)?;
+    if matches!(type_id, 5..=7) && arguments.len() != 2 {
+        return Err(p.error(format!(
+            "exactly two operands for `{}`, got {}",
+            name,
+            arguments.len()
+        )));
+    }
+    token(p, ")")?;
+
+    Ok(operator(type_id, arguments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::decoder::decode;
+    use super::super::interpreter::interpret;
+    use super::*;
+    use crate::error::SolveError;
+    use crate::helpers::parse::ParseError;
+
+    fn evaluate(expression: &str) -> SolveResult<u64> {
+        Ok(interpret(&decode(&compile(expression)?)?)?)
+    }
+
+    #[test]
+    fn test_compile() {
+        assert_eq!(evaluate("min(3, 7*2) + (5 > 2)"), Ok(4));
+        assert_eq!(evaluate("2021"), Ok(2021));
+        assert_eq!(evaluate("1 + 2 * 3 + 4"), Ok(11));
+        assert_eq!(evaluate("(1 + 2) * (3 + 4)"), Ok(21));
+        assert_eq!(evaluate("1 + 3 == 2 * 2"), Ok(1));
+        assert_eq!(evaluate("lt(max(1, 9, 4), 8)"), Ok(0));
+        assert_eq!(evaluate("product(sum(1, 2), eq(5, 5))"), Ok(3));
+        assert_eq!(evaluate("  ( 4 )<5 "), Ok(1));
+
+        // the puzzle's `1 + 3 = 2 * 2` example.
+        assert_eq!(
+            compile("eq(sum(1, 3), product(2, 2))"),
+            compile("1+3 == 2*2")
+        );
+        // operands are flattened into a single operator.
+        let packet = parse("1 + 2 + 3").unwrap();
+        assert!(matches!(packet, Packet::Operator(ref data) if data.children.len() == 3));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        let error = |column: usize, expected: &str| {
+            Err(SolveError::Parse(ParseError {
+                line: 1,
+                column,
+                expected: expected.into(),
+            }))
+        };
+
+        assert_eq!(
+            parse("gt(1, 2, 3)"),
+            error(11, "exactly two operands for `gt`, got 3")
+        );
+        assert_eq!(
+            parse("eq(1)"),
+            error(5, "exactly two operands for `eq`, got 1")
+        );
+        assert_eq!(
+            parse("1 < 2 < 3"),
+            error(7, "end of comparison, comparisons can not be chained")
+        );
+        assert_eq!(parse("2 - 1"), error(3, "end of line"));
+        assert_eq!(parse("(1 + 2"), error(7, "`)`"));
+        assert_eq!(parse(""), error(1, "integer, `(` or function name"));
+        assert!(parse("avg(1, 2)").is_err());
+        assert!(parse("min()").is_err());
+        assert!(parse("99999999999999999999").is_err());
+    }
+
+    #[test]
+    fn test_nesting() {
+        let nested = |depth: usize| "(".repeat(depth) + "1" + &")".repeat(depth);
+        assert_eq!(evaluate(&nested(100)), Ok(1));
+        assert_eq!(
+            evaluate(&("sum(".repeat(100) + "2" + &")".repeat(100))),
+            Ok(2)
+        );
+
+        let error = Err(SolveError::Parse(ParseError {
+            line: 1,
+            column: 101,
+            expected: "parentheses nested at most 100 levels deep".into(),
+        }));
+        assert_eq!(parse(&nested(101)), error);
+        assert_eq!(parse(&"(".repeat(200_000)), error);
+        // calls count like any other parentheses, the 101st `min(` opens at column 404.
+        assert!(matches!(
+            parse(&"min(".repeat(200_000)),
+            Err(SolveError::Parse(ParseError { column: 404, .. }))
+        ));
+    }
+}
@@ -0,0 +1,595 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::ParseError;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Packet {
+    Operator(Operator),
+    Literal(Literal),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Literal {
+    pub header: Header,
+    pub value: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Operator {
+    pub header: Header,
+    pub length_type: LengthType,
+    pub children: Vec<Packet>,
+}
+
+/// Sub-packets are dropped with an explicit stack, see `Packet::walk`.
+impl Drop for Operator {
+    fn drop(&mut self) {
+        let mut packets = std::mem::take(&mut self.children);
+
+        while let Some(packet) = packets.pop() {
+            if let Packet::Operator(mut data) = packet {
+                packets.append(&mut data.children);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub version: u64,
+    pub type_id: u64,
+}
+
+/// How an operator announces the extent of its sub-packets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthType {
+    /// length type `0`: the total length of all sub-packets in bits, as a 15-bit number.
+    TotalLength,
+    /// length type `1`: the number of sub-packets, as an 11-bit number.
+    PacketCount,
+}
+
+/// A step of a depth-first walk over a packet tree, see `Packet::walk`.
+pub enum Step<'a> {
+    /// a packet is reached, before any of its sub-packets.
+    Enter(&'a Packet),
+    /// all sub-packets of an operator have been visited.
+    Leave(&'a Operator),
+}
+
+/// Iterator returned by `Packet::walk`.
+pub struct Walk<'a> {
+    /// the outermost packet, until it is entered.
+    root: Option<&'a Packet>,
+    /// the operator entered by the last step, it is opened on the next step.
+    entered: Option<&'a Operator>,
+    /// operators that have been opened but not left, with their remaining sub-packets. innermost last.
+    open: Vec<(&'a Operator, std::slice::Iter<'a, Packet>)>,
+}
+
+impl Walk<'_> {
+    /// number of operators around the packet of the last step.
+    pub fn depth(&self) -> usize {
+        self.open.len()
+    }
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = Step<'a>;
+
+    fn next(&mut self) -> Option<Step<'a>> {
+        if let Some(operator) = self.entered.take() {
+            self.open.push((operator, operator.children.iter()));
+        }
+
+        let packet = match self.root.take() {
+            Some(packet) => packet,
+            None => {
+                let (operator, children) = self.open.last_mut()?;
+
+                match children.next() {
+                    Some(child) => child,
+                    None => {
+                        let operator = *operator;
+                        self.open.pop();
+                        return Some(Step::Leave(operator));
+                    }
+                }
+            }
+        };
+
+        if let Packet::Operator(data) = packet {
+            self.entered = Some(data);
+        }
+
+        Some(Step::Enter(packet))
+    }
+}
+
+impl Packet {
+    pub fn header(&self) -> &Header {
+        match self {
+            Packet::Literal(data) => &data.header,
+            Packet::Operator(data) => &data.header,
+        }
+    }
+
+    /// Walk the packet tree depth-first, sub-packets in order.
+    /// The walk keeps its own stack, so transmissions can nest deeper than the call stack would allow.
+    pub fn walk(&self) -> Walk<'_> {
+        Walk {
+            root: Some(self),
+            entered: None,
+            open: Vec::new(),
+        }
+    }
+}
+
+/// error returned when a transmission can not be decoded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    /// bit offset in the message where the problem was found, e.g. the start of the offending packet.
+    pub offset: usize,
+    pub reason: String,
+}
+
+impl DecodeError {
+    fn new(offset: usize, reason: impl Into<String>) -> Self {
+        DecodeError {
+            offset,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bit {}: {}", self.offset, self.reason)
+    }
+}
+
+impl Error for DecodeError {}
+
+impl From<DecodeError> for SolveError {
+    fn from(err: DecodeError) -> Self {
+        SolveError::invalid(err.to_string())
+    }
+}
+
+pub type DecodeResult<T> = Result<T, DecodeError>;
+
+pub fn decode(message: &str) -> SolveResult<Packet> {
+    Ok(Decoder::new(hex_reader(message)?, false).packet()?)
+}
+
+/// decode `message` together with the bit offset of every packet, in the order they appear in the message.
+pub fn decode_with_offsets(message: &str) -> SolveResult<(Packet, Vec<usize>)> {
+    let mut decoder = Decoder::new(hex_reader(message)?, false).with_offsets();
+    let packet = decoder.packet()?;
+    Ok((packet, decoder.offsets.unwrap_or_default()))
+}
+
+/// Decode `message`, rejecting everything that is not a well-formed transmission.
+/// On top of what `decode` checks, operators need at least one sub-packet, comparisons exactly two,
+/// and the bits after the outermost packet need to be zero padding.
+/// `interpreter::interpret` accepts every packet this returns.
+pub fn decode_strict(message: &str) -> DecodeResult<Packet> {
+    let mut decoder = Decoder::new(BitReader::new(message)?, true);
+    let packet = decoder.packet()?;
+
+    while decoder.reader.remaining() > 0 {
+        let offset = decoder.reader.position;
+        if decoder.reader.read(1)? != 0 {
+            return Err(DecodeError::new(offset, "padding needs to be zero"));
+        }
+    }
+
+    Ok(packet)
+}
+
+/// index and value of the first character of `message` that is not a hexadecimal digit.
+fn invalid_digit(message: &str) -> Option<(usize, char)> {
+    message
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !matches!(c, '0'..='9' | 'A'..='F'))
+}
+
+/// like `BitReader::new`, but invalid digits are reported with line and column like any other input error.
+fn hex_reader(message: &str) -> SolveResult<BitReader<'_>> {
+    if let Some((i, _)) = invalid_digit(message) {
+        return Err(ParseError {
+            line: 1,
+            column: i + 1,
+            expected: "hexadecimal digit".into(),
+        }
+        .into());
+    }
+
+    Ok(BitReader::new(message)?)
+}
+
+/// A cursor over the bits of a hexadecimal message.
+/// Bits are read straight from the characters of the message, so decoding does not allocate per bit.
+struct BitReader<'a> {
+    hex: &'a [u8],
+    /// index of the next bit.
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    /// check that `message` only consists of hexadecimal digits.
+    fn new(message: &'a str) -> DecodeResult<Self> {
+        if let Some((i, c)) = invalid_digit(message) {
+            return Err(DecodeError::new(
+                i * 4,
+                format!("expected hexadecimal digit, found `{}`", c),
+            ));
+        }
+
+        Ok(BitReader {
+            hex: message.as_bytes(),
+            position: 0,
+        })
+    }
+
+    fn remaining(&self) -> usize {
+        self.hex.len() * 4 - self.position
+    }
+
+    /// read the next `width` (at most 64) bits as a number, most significant bit first.
+    fn read(&mut self, width: usize) -> DecodeResult<u64> {
+        if width > self.remaining() {
+            return Err(DecodeError::new(
+                self.position,
+                format!(
+                    "message is truncated, expected {} more bits but found {}",
+                    width,
+                    self.remaining()
+                ),
+            ));
+        }
+
+        let mut value = 0;
+        let mut remaining = width;
+
+        // take as many bits as possible from every digit instead of going bit by bit.
+        while remaining > 0 {
+            let nibble = match self.hex[self.position / 4] {
+                digit @ b'0'..=b'9' => digit - b'0',
+                digit => digit - b'A' + 10,
+            } as u64;
+            let used = self.position % 4;
+            let take = (4 - used).min(remaining);
+
+            value = (value << take) | ((nibble >> (4 - used - take)) & ((1 << take) - 1));
+            self.position += take;
+            remaining -= take;
+        }
+
+        Ok(value)
+    }
+}
+
+/// an operator whose sub-packets are still being decoded.
+struct Open {
+    offset: usize,
+    header: Header,
+    length_type: LengthType,
+    /// bit position of the first sub-packet.
+    start: usize,
+    /// bit position where the sub-packets end for `TotalLength`, number of sub-packets for `PacketCount`.
+    extent: usize,
+    children: Vec<Packet>,
+}
+
+struct Decoder<'a> {
+    reader: BitReader<'a>,
+    /// bit offset of every decoded packet, only recorded if requested with `with_offsets`.
+    offsets: Option<Vec<usize>>,
+    strict: bool,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(reader: BitReader<'a>, strict: bool) -> Self {
+        Decoder {
+            reader,
+            offsets: None,
+            strict,
+        }
+    }
+
+    fn with_offsets(mut self) -> Self {
+        self.offsets = Some(Vec::new());
+        self
+    }
+
+    /// decode the packet at the position of the reader.
+    /// operators are kept on an explicit stack until all of their sub-packets are decoded,
+    /// so the nesting depth of a transmission is only limited by memory.
+    fn packet(&mut self) -> DecodeResult<Packet> {
+        let mut open: Vec<Open> = Vec::new();
+
+        loop {
+            let offset = self.reader.position;
+            if let Some(offsets) = &mut self.offsets {
+                offsets.push(offset);
+            }
+
+            let header = Header {
+                version: self.reader.read(3)?,
+                type_id: self.reader.read(3)?,
+            };
+
+            let mut done = if header.type_id == 4 {
+                Some(self.literal(offset, header)?)
+            } else {
+                open.push(self.open_operator(offset, header)?);
+                None
+            };
+
+            // hand finished packets to their parents, closing every operator that has all of its sub-packets.
+            loop {
+                match (done.take(), open.last_mut()) {
+                    (Some(packet), None) => return Ok(packet),
+                    (Some(packet), Some(parent)) => parent.children.push(packet),
+                    (None, _) => {}
+                }
+
+                // an operator was opened or a packet handed to its parent, so `open` is not empty.
+                let parent = open.last().unwrap();
+                let complete = match parent.length_type {
+                    LengthType::TotalLength => self.reader.position >= parent.extent,
+                    LengthType::PacketCount => parent.children.len() == parent.extent,
+                };
+
+                if !complete {
+                    break;
+                }
+
+                done = Some(self.close_operator(open.pop().unwrap())?);
+            }
+        }
+    }
+
+    fn literal(&mut self, offset: usize, header: Header) -> DecodeResult<Packet> {
+        let mut value: u64 = 0;
+        let mut groups = 0;
+
+        loop {
+            let more = self.reader.read(1)? == 1;
+            groups += 1;
+
+            if groups > 16 {
+                return Err(DecodeError::new(offset, "literal does not fit into u64"));
+            }
+
+            value = (value << 4) | self.reader.read(4)?;
+
+            if !more {
+                break;
+            }
+        }
+
+        Ok(Packet::Literal(Literal { header, value }))
+    }
+
+    fn open_operator(&mut self, offset: usize, header: Header) -> DecodeResult<Open> {
+        let (length_type, extent) = match self.reader.read(1)? {
+            0 => {
+                let len = self.reader.read(15)? as usize;
+                (LengthType::TotalLength, self.reader.position + len)
+            }
+            _ => (LengthType::PacketCount, self.reader.read(11)? as usize),
+        };
+
+        Ok(Open {
+            offset,
+            header,
+            length_type,
+            start: self.reader.position,
+            extent,
+            children: Vec::new(),
+        })
+    }
+
+    fn close_operator(&self, operator: Open) -> DecodeResult<Packet> {
+        let Open {
+            offset,
+            header,
+            length_type,
+            start,
+            extent,
+            children,
+        } = operator;
+
+        if length_type == LengthType::TotalLength && self.reader.position != extent {
+            return Err(DecodeError::new(
+                offset,
+                format!(
+                    "sub-packets take up {} bits, but the operator declares {}",
+                    self.reader.position - start,
+                    extent - start
+                ),
+            ));
+        }
+
+        if self.strict {
+            match (header.type_id, children.len()) {
+                (_, 0) => {
+                    return Err(DecodeError::new(offset, "operator has no sub-packets"));
+                }
+                (5..=7, n) if n != 2 => {
+                    return Err(DecodeError::new(
+                        offset,
+                        format!("comparison needs exactly two sub-packets, found {}", n),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Packet::Operator(Operator {
+            header,
+            length_type,
+            children,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+    use crate::helpers::rng::Rng;
+    use crate::solutions::day16::encoder::{encode, with_length_type};
+    use crate::solutions::day16::interpreter::{interpret, sum_versions};
+
+    fn literal(value: u64) -> Packet {
+        Packet::Literal(Literal {
+            header: Header {
+                version: 0,
+                type_id: 4,
+            },
+            value,
+        })
+    }
+
+    fn operator(type_id: u64, children: Vec<Packet>) -> Packet {
+        Packet::Operator(Operator {
+            header: Header {
+                version: 0,
+                type_id,
+            },
+            length_type: LengthType::PacketCount,
+            children,
+        })
+    }
+
+    #[test]
+    fn test_read() {
+        let mut reader = BitReader::new("D2FE28").unwrap();
+        assert_eq!(reader.read(3), Ok(6));
+        assert_eq!(reader.read(3), Ok(4));
+        assert_eq!(reader.read(5), Ok(0b10111));
+        assert_eq!(reader.read(13), Ok(0b1111000101000));
+        assert_eq!(reader.position, 24);
+        assert!(reader.read(1).is_err());
+
+        let mut reader = BitReader::new("FFFFFFFFFFFFFFFF0").unwrap();
+        assert_eq!(reader.read(2), Ok(3));
+        assert_eq!(reader.read(64), Ok(u64::MAX << 2));
+
+        assert!(BitReader::new("D2fE28").is_err());
+    }
+
+    #[test]
+    fn test_decode_large() {
+        // a few megabytes of hex, decoding needs to stay linear in the length of the message.
+        let message = generate::day16(&mut Rng::new(0), 50_000);
+        assert!(message.len() > 1_000_000);
+
+        let packet = decode(message.trim_end()).unwrap();
+        assert!(interpret(&packet).is_ok());
+        assert!(sum_versions(&packet) > 0);
+    }
+
+    #[test]
+    fn test_decode_with_offsets() {
+        let (packet, offsets) = decode_with_offsets("38006F45291200").unwrap();
+        assert_eq!(decode("38006F45291200"), Ok(packet));
+        assert_eq!(offsets, vec![0, 22, 33]);
+    }
+
+    /// `depth` operators that each wrap the next one, around a single literal.
+    fn nested(depth: usize) -> Packet {
+        (0..depth).fold(literal(7), |packet, i| operator(i as u64 % 4, vec![packet]))
+    }
+
+    #[test]
+    fn test_decode_deep() {
+        // far deeper than recursing once per level would allow, even in a release build.
+        let message = encode(&nested(30_000)).unwrap();
+        assert!(message.len() > 100_000);
+
+        let packet = decode(&message).unwrap();
+        assert_eq!(interpret(&packet), Ok(7));
+        assert_eq!(sum_versions(&packet), 0);
+        assert_eq!(
+            encode(&with_length_type(&packet, LengthType::PacketCount)).as_deref(),
+            Ok(message.as_str())
+        );
+        let (_, offsets) = decode_with_offsets(&message).unwrap();
+        assert_eq!(offsets.len(), 30_001);
+        assert_eq!(offsets[30_000], 30_000 * 18);
+    }
+
+    #[test]
+    fn test_decode_strict() {
+        for message in ["D2FE28", "8A004A801A8002F478", "9C0141080250320F1802104A08"] {
+            assert_eq!(decode_strict(message).ok(), decode(message).ok());
+        }
+        for seed in 0..10 {
+            let message = generate::day16(&mut Rng::new(seed), 20);
+            assert!(decode_strict(message.trim_end()).is_ok());
+        }
+
+        let error = |offset, reason: &str| Err(DecodeError::new(offset, reason));
+
+        assert_eq!(
+            decode_strict("D2FE"),
+            error(16, "message is truncated, expected 1 more bits but found 0")
+        );
+        assert_eq!(
+            decode_strict("D2XE28"),
+            error(8, "expected hexadecimal digit, found `X`")
+        );
+        assert_eq!(
+            decode_strict("D2FE29"),
+            error(23, "padding needs to be zero")
+        );
+        // `38006F45291200` with a declared length of 26 instead of 27 bits.
+        assert_eq!(
+            decode_strict("38006B45291200"),
+            error(
+                0,
+                "sub-packets take up 27 bits, but the operator declares 26"
+            )
+        );
+
+        let empty = encode(&operator(0, vec![])).unwrap();
+        assert!(decode(&empty).is_ok());
+        assert_eq!(
+            decode_strict(&empty),
+            error(0, "operator has no sub-packets")
+        );
+
+        // malformed transmissions are rejected no matter how deeply they nest.
+        let deep = encode(&nested(30_000)).unwrap();
+        assert_eq!(
+            decode_strict(&deep).map(|packet| sum_versions(&packet)),
+            Ok(0)
+        );
+        assert_eq!(
+            decode_strict(&deep[..deep.len() / 2]),
+            error(
+                270_007,
+                "message is truncated, expected 11 more bits but found 1"
+            )
+        );
+        let empty = (0..30_000).fold(operator(0, vec![]), |packet, _| operator(1, vec![packet]));
+        assert_eq!(
+            decode_strict(&encode(&empty).unwrap()),
+            error(30_000 * 18, "operator has no sub-packets")
+        );
+
+        let comparison = operator(1, vec![operator(5, vec![literal(1); 3])]);
+        let message = encode(&comparison).unwrap();
+        assert_eq!(decode(&message), Ok(comparison));
+        assert_eq!(
+            decode_strict(&message),
+            error(18, "comparison needs exactly two sub-packets, found 3")
+        );
+    }
+}
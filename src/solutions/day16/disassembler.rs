@@ -0,0 +1,157 @@
+use super::decoder::{decode_with_offsets, LengthType, Packet, Step};
+use crate::error::SolveResult;
+use std::fmt::Write;
+
+/// names of the packet types, indexed by type id.
+const TYPE_NAMES: [&str; 8] = ["sum", "product", "min", "max", "literal", "gt", "lt", "eq"];
+
+fn type_name(type_id: u64) -> &'static str {
+    TYPE_NAMES.get(type_id as usize).unwrap_or(&"unknown")
+}
+
+/// Decode `message` and list its packets, one per line and indented by depth.
+/// Every line starts with the bit offset of the packet in the message, followed by its version and type.
+/// Operators show their length type and number of sub-packets, literals their value.
+pub fn disassemble(message: &str) -> SolveResult<String> {
+    let (packet, offsets) = decode_with_offsets(message)?;
+    let mut offsets = offsets.into_iter();
+    let mut out = String::new();
+    let mut walk = packet.walk();
+
+    while let Some(step) = walk.next() {
+        if let Step::Enter(packet) = step {
+            list(packet, walk.depth(), offsets.next().unwrap(), &mut out);
+        }
+    }
+
+    Ok(out)
+}
+
+/// write the line for a single packet.
+fn list(packet: &Packet, depth: usize, offset: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+
+    match packet {
+        Packet::Literal(data) => writeln!(
+            out,
+            "{:>5}  {}v{} literal {}",
+            offset, indent, data.header.version, data.value
+        )
+        .unwrap(),
+        Packet::Operator(data) => {
+            let length_type = match data.length_type {
+                LengthType::TotalLength => "total length",
+                LengthType::PacketCount => "packet count",
+            };
+            writeln!(
+                out,
+                "{:>5}  {}v{} {} ({}, {} sub-packets)",
+                offset,
+                indent,
+                data.header.version,
+                type_name(data.header.type_id),
+                length_type,
+                data.children.len()
+            )
+            .unwrap();
+        }
+    }
+}
+
+/// Render `packet` as an expression that calls every operator by name, e.g. `sum(1, product(2, 3))`.
+/// The result can be compiled back with `compiler::compile`.
+pub fn expression(packet: &Packet) -> String {
+    let mut out = String::new();
+
+    for step in packet.walk() {
+        // every operand but the first follows a completed operand, which ends with a digit or `)`.
+        if matches!(step, Step::Enter(_)) && !(out.is_empty() || out.ends_with('(')) {
+            out.push_str(", ");
+        }
+
+        match step {
+            Step::Enter(Packet::Literal(data)) => write!(out, "{}", data.value).unwrap(),
+            Step::Enter(Packet::Operator(data)) => {
+                write!(out, "{}(", type_name(data.header.type_id)).unwrap()
+            }
+            Step::Leave(_) => out.push(')'),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::compiler::parse;
+    use super::super::decoder::{decode, Header, Operator};
+    use super::super::encoder::encode;
+    use super::*;
+
+    /// `sum(sum(...sum(1)...))`, built bottom-up since the compiler only accepts 100 levels of parentheses.
+    fn nested(depth: usize) -> String {
+        let packet = (0..depth).fold(parse("1").unwrap(), |packet, _| {
+            Packet::Operator(Operator {
+                header: Header {
+                    version: 0,
+                    type_id: 0,
+                },
+                length_type: LengthType::PacketCount,
+                children: vec![packet],
+            })
+        });
+        encode(&packet).unwrap()
+    }
+
+    #[test]
+    fn test_disassemble() {
+        assert_eq!(
+            disassemble("8A004A801A8002F478").unwrap(),
+            [
+                "    0  v4 min (packet count, 1 sub-packets)",
+                "   18    v1 min (packet count, 1 sub-packets)",
+                "   36      v5 min (total length, 1 sub-packets)",
+                "   58        v6 literal 15",
+                "",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            disassemble("38006F45291200").unwrap(),
+            [
+                "    0  v1 lt (total length, 2 sub-packets)",
+                "   22    v6 literal 10",
+                "   33    v2 literal 20",
+                "",
+            ]
+            .join("\n")
+        );
+        assert!(disassemble("38006F").is_err());
+    }
+
+    #[test]
+    fn test_expression() {
+        let packet = decode("9C0141080250320F1802104A08").unwrap();
+        assert_eq!(expression(&packet), "eq(sum(1, 3), product(2, 2))");
+
+        let packet = parse("min(3, 7*2) + (5 > 2)").unwrap();
+        assert_eq!(expression(&packet), "sum(min(3, product(7, 2)), gt(5, 2))");
+        assert_eq!(parse(&expression(&packet)), Ok(packet));
+    }
+
+    #[test]
+    fn test_deep() {
+        let listing = disassemble(&nested(2_000)).unwrap();
+        assert_eq!(listing.lines().count(), 2_001);
+        assert_eq!(
+            listing.lines().last().unwrap(),
+            format!("36000  {}v0 literal 1", "  ".repeat(2_000))
+        );
+
+        let packet = decode(&nested(30_000)).unwrap();
+        assert_eq!(
+            expression(&packet),
+            "sum(".repeat(30_000) + "1" + &")".repeat(30_000)
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use super::decoder::{Header, LengthType, Literal, Operator, Packet, Step};
+use crate::error::{SolveError, SolveResult};
+
+/// Serialize a packet tree to a hexadecimal transmission, the inverse of `decoder::decode`.
+/// Operators announce their sub-packets with their own `length_type`, literals use as few groups as possible.
+/// The transmission is padded with zeros to whole bytes, like the puzzle's examples.
+pub fn encode(packet: &Packet) -> SolveResult<String> {
+    let mut bits = Vec::new();
+    encode_packet(packet, &mut bits)?;
+    bits.resize(bits.len().next_multiple_of(8), 0);
+
+    Ok(bits
+        .chunks(4)
+        .map(|nibble| format!("{:X}", nibble.iter().fold(0, |acc, &b| acc * 2 + b)))
+        .collect())
+}
+
+/// Set the length type of every operator in the tree.
+pub fn with_length_type(packet: &Packet, length_type: LengthType) -> Packet {
+    // copied sub-packets of every open operator, innermost last. the first entry collects the result.
+    let mut copies: Vec<Vec<Packet>> = vec![Vec::new()];
+
+    for step in packet.walk() {
+        let copy = match step {
+            Step::Enter(Packet::Literal(data)) => Packet::Literal(data.clone()),
+            Step::Enter(Packet::Operator(_)) => {
+                copies.push(Vec::new());
+                continue;
+            }
+            Step::Leave(data) => Packet::Operator(Operator {
+                header: data.header.clone(),
+                length_type,
+                children: copies.pop().unwrap(),
+            }),
+        };
+
+        copies.last_mut().unwrap().push(copy);
+    }
+
+    copies.pop().unwrap().pop().unwrap()
+}
+
+/// append the lowest `width` bits of `value`, most significant bit first.
+fn push_bits(bits: &mut Vec<u8>, value: u64, width: usize) {
+    bits.extend((0..width).rev().map(|i| ((value >> i) & 1) as u8));
+}
+
+/// overwrite `width` bits starting at `at` with the lowest bits of `value`, most significant bit first.
+fn set_bits(bits: &mut [u8], at: usize, value: u64, width: usize) {
+    for (i, bit) in bits[at..at + width].iter_mut().enumerate() {
+        *bit = ((value >> (width - 1 - i)) & 1) as u8;
+    }
+}
+
+/// operators get their length field reserved when they are entered and filled in once all of their sub-packets
+/// are encoded, so the tree is encoded in a single pass without recursion.
+fn encode_packet(packet: &Packet, bits: &mut Vec<u8>) -> SolveResult<()> {
+    // position of the length field of every open operator, innermost last.
+    let mut fields: Vec<usize> = Vec::new();
+
+    for step in packet.walk() {
+        match step {
+            Step::Enter(Packet::Literal(data)) => encode_literal(data, bits)?,
+            Step::Enter(Packet::Operator(data)) => {
+                fields.push(open_operator(data, bits)?);
+            }
+            Step::Leave(data) => close_operator(data, fields.pop().unwrap(), bits)?,
+        }
+    }
+
+    Ok(())
+}
+
+fn encode_header(header: &Header, bits: &mut Vec<u8>) -> SolveResult<()> {
+    if header.version > 7 || header.type_id > 7 {
+        return Err(SolveError::invalid(format!(
+            "{:?} does not fit into 3-bit fields",
+            header
+        )));
+    }
+
+    push_bits(bits, header.version, 3);
+    push_bits(bits, header.type_id, 3);
+    Ok(())
+}
+
+fn encode_literal(literal: &Literal, bits: &mut Vec<u8>) -> SolveResult<()> {
+    if literal.header.type_id != 4 {
+        return Err(SolveError::invalid("literals need to have type_id 4"));
+    }
+
+    encode_header(&literal.header, bits)?;
+
+    // every group holds 4 bits of the value and a leading bit that marks whether more groups follow.
+    let groups = (64 - literal.value.leading_zeros() as usize)
+        .div_ceil(4)
+        .max(1);
+    for i in (0..groups).rev() {
+        bits.push((i > 0) as u8);
+        push_bits(bits, literal.value >> (i * 4), 4);
+    }
+
+    Ok(())
+}
+
+/// width of the length field of an operator, including the length type bit.
+fn field_width(length_type: LengthType) -> usize {
+    match length_type {
+        LengthType::TotalLength => 16,
+        LengthType::PacketCount => 12,
+    }
+}
+
+/// encode the header of `operator` and reserve its length field, returns the position of the field.
+fn open_operator(operator: &Operator, bits: &mut Vec<u8>) -> SolveResult<usize> {
+    if operator.header.type_id == 4 {
+        return Err(SolveError::invalid("type_id 4 is reserved for literals"));
+    }
+
+    encode_header(&operator.header, bits)?;
+
+    let field = bits.len();
+    bits.resize(field + field_width(operator.length_type), 0);
+    Ok(field)
+}
+
+/// fill in the length field at `field` once the sub-packets of `operator` are encoded.
+fn close_operator(operator: &Operator, field: usize, bits: &mut [u8]) -> SolveResult<()> {
+    let len = bits.len() - field - field_width(operator.length_type);
+
+    match operator.length_type {
+        LengthType::TotalLength if len < 1 << 15 => {
+            set_bits(bits, field, len as u64, 16);
+        }
+        LengthType::PacketCount if operator.children.len() < 1 << 11 => {
+            set_bits(bits, field, (1 << 11) | operator.children.len() as u64, 12);
+        }
+        length_type => {
+            return Err(SolveError::invalid(format!(
+                "sub-packets do not fit into an operator with {:?}",
+                length_type
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::decoder::decode;
+    use super::*;
+
+    fn literal(version: u64, value: u64) -> Packet {
+        Packet::Literal(Literal {
+            header: Header {
+                version,
+                type_id: 4,
+            },
+            value,
+        })
+    }
+
+    fn operator(type_id: u64, length_type: LengthType, children: Vec<Packet>) -> Packet {
+        Packet::Operator(Operator {
+            header: Header {
+                version: 1,
+                type_id,
+            },
+            length_type,
+            children,
+        })
+    }
+
+    #[test]
+    fn test_encode_examples() {
+        // transmissions from the puzzle description, these are padded to whole bytes.
+        for message in [
+            "D2FE28",
+            "38006F45291200",
+            "EE00D40C823060",
+            "8A004A801A8002F478",
+            "620080001611562C8802118E34",
+            "C0015000016115A2E0802F182340",
+            "A0016C880162017C3686B18A3D4780",
+            "9C0141080250320F1802104A08",
+        ] {
+            let packet = decode(message).unwrap();
+            assert_eq!(encode(&packet).as_deref(), Ok(message));
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let packet = operator(
+            0,
+            LengthType::TotalLength,
+            vec![
+                literal(6, 0),
+                literal(2, 2021),
+                operator(1, LengthType::PacketCount, vec![literal(0, u64::MAX)]),
+            ],
+        );
+
+        for length_type in [LengthType::TotalLength, LengthType::PacketCount] {
+            let packet = with_length_type(&packet, length_type);
+            assert_eq!(decode(&encode(&packet).unwrap()), Ok(packet));
+        }
+
+        // `D2FE28` encodes 2021 in three groups and a version of 6.
+        assert_eq!(encode(&literal(6, 2021)).as_deref(), Ok("D2FE28"));
+        // zero still needs a single group.
+        assert_eq!(encode(&literal(0, 0)).as_deref(), Ok("1000"));
+    }
+
+    #[test]
+    fn test_round_trip_generated() {
+        use crate::generate;
+        use crate::helpers::rng::Rng;
+
+        for seed in 0..20 {
+            let message = generate::day16(&mut Rng::new(seed), 5);
+            let packet = decode(message.trim_end()).unwrap();
+            let encoded = encode(&packet).unwrap();
+
+            assert_eq!(decode(&encoded), Ok(packet.clone()));
+            for length_type in [LengthType::TotalLength, LengthType::PacketCount] {
+                let packet = with_length_type(&packet, length_type);
+                assert_eq!(decode(&encode(&packet).unwrap()), Ok(packet));
+            }
+        }
+    }
+
+    #[test]
+    fn test_unencodable() {
+        assert!(encode(&literal(8, 1)).is_err());
+        assert!(encode(&operator(4, LengthType::PacketCount, vec![])).is_err());
+
+        let many = operator(0, LengthType::PacketCount, vec![literal(0, 1); 2048]);
+        assert!(encode(&many).is_err());
+        assert!(encode(&with_length_type(&many, LengthType::TotalLength)).is_ok());
+    }
+}
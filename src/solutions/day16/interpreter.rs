@@ -0,0 +1,208 @@
+use super::decoder::{Operator, Packet, Step};
+use crate::error::SolveError;
+use crate::helpers::bignum::BigUint;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// error returned when a packet tree can not be evaluated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvalError {
+    /// indices of the sub-packets leading from the outermost packet to the offending one.
+    pub path: Vec<usize>,
+    pub reason: String,
+}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "packet root")?;
+        for index in &self.path {
+            write!(f, ".{}", index)?;
+        }
+        write!(f, ": {}", self.reason)
+    }
+}
+
+impl Error for EvalError {}
+
+impl From<EvalError> for SolveError {
+    fn from(err: EvalError) -> Self {
+        SolveError::invalid(err.to_string())
+    }
+}
+
+pub type EvalResult<T> = Result<T, EvalError>;
+
+/// numbers that packets can be evaluated to.
+trait Value: Ord + Sized {
+    fn literal(value: u64) -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+}
+
+impl Value for u64 {
+    fn literal(value: u64) -> Self {
+        value
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        u64::checked_add(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        u64::checked_mul(*self, *other)
+    }
+}
+
+impl Value for BigUint {
+    fn literal(value: u64) -> Self {
+        BigUint::from(value)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+}
+
+/// Evaluate `packet` with checked `u64` arithmetic. Fails with the path of the packet whose sum or product
+/// overflows, or whose operator can not take its number of operands.
+pub fn interpret(packet: &Packet) -> EvalResult<u64> {
+    evaluate(packet)
+}
+
+/// Evaluate `packet` exactly, no matter how large intermediate results get.
+/// Only fails for operators that can not take their number of operands.
+pub fn interpret_exact(packet: &Packet) -> EvalResult<BigUint> {
+    evaluate(packet)
+}
+
+/// evaluates the tree bottom-up with a stack of operands, so deeply nested packets do not overflow the call stack.
+fn evaluate<V: Value>(packet: &Packet) -> EvalResult<V> {
+    // values of the sub-packets of every open operator, innermost last. the first entry collects the result.
+    let mut operands: Vec<Vec<V>> = vec![Vec::new()];
+    // index of every open operator among its siblings, starting with `0` for the outermost packet.
+    let mut indices: Vec<usize> = Vec::new();
+
+    for step in packet.walk() {
+        let value = match step {
+            Step::Enter(Packet::Literal(data)) => V::literal(data.value),
+            Step::Enter(Packet::Operator(_)) => {
+                indices.push(operands.last().unwrap().len());
+                operands.push(Vec::new());
+                continue;
+            }
+            Step::Leave(data) => {
+                let values = operands.pop().unwrap();
+                let value = apply(data, values).map_err(|reason| EvalError {
+                    path: indices[1..].to_vec(),
+                    reason,
+                })?;
+                indices.pop();
+                value
+            }
+        };
+
+        operands.last_mut().unwrap().push(value);
+    }
+
+    Ok(operands.pop().unwrap().pop().unwrap())
+}
+
+/// apply an operator to the values of its sub-packets, or describe why it can not be applied.
+fn apply<V: Value>(data: &Operator, values: Vec<V>) -> Result<V, String> {
+    let type_id = data.header.type_id;
+
+    match (type_id, values.len()) {
+        (0, _) => values
+            .iter()
+            .try_fold(V::literal(0), |acc, value| acc.checked_add(value))
+            .ok_or_else(|| "sum overflows u64".to_string()),
+        (1, _) => values
+            .iter()
+            .try_fold(V::literal(1), |acc, value| acc.checked_mul(value))
+            .ok_or_else(|| "product overflows u64".to_string()),
+        (2, 1..) => Ok(values.into_iter().min().unwrap()),
+        (3, 1..) => Ok(values.into_iter().max().unwrap()),
+        (5, 2) => Ok(V::literal((values[0] > values[1]) as u64)),
+        (6, 2) => Ok(V::literal((values[0] < values[1]) as u64)),
+        (7, 2) => Ok(V::literal((values[0] == values[1]) as u64)),
+        (2 | 3 | 5 | 6 | 7, _) => Err(format!(
+            "operator {} can not take {} operands",
+            type_id,
+            values.len()
+        )),
+        _ => Err(format!("unknown type_id {}", type_id)),
+    }
+}
+
+pub fn sum_versions(packet: &Packet) -> u64 {
+    packet
+        .walk()
+        .filter_map(|step| match step {
+            Step::Enter(packet) => Some(packet.header().version),
+            Step::Leave(_) => None,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::compiler::parse;
+    use super::*;
+
+    #[test]
+    fn test_interpret() {
+        let interpret = |expression| interpret(&parse(expression).unwrap());
+
+        assert_eq!(interpret("sum(1, 2, 3) * 2"), Ok(12));
+        assert_eq!(interpret("min(4, 2, 8) + max(4, 2, 8)"), Ok(10));
+        assert_eq!(interpret("(3 > 2) + (3 < 2) + (3 == 2)"), Ok(1));
+        assert_eq!(interpret("product(7)"), Ok(7));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let packet = parse("sum(1, product(18446744073709551615, 2))").unwrap();
+        let err = interpret(&packet).unwrap_err();
+        assert_eq!(err.path, vec![1]);
+        assert_eq!(err.to_string(), "packet root.1: product overflows u64");
+        assert_eq!(
+            interpret_exact(&packet).map(|value| value.to_string()),
+            Ok("36893488147419103231".into())
+        );
+
+        let packet = parse("max(1, 2 + (3 < 18446744073709551615 + 1))").unwrap();
+        assert_eq!(interpret(&packet).unwrap_err().path, vec![1, 1, 1]);
+        assert_eq!(interpret_exact(&packet).unwrap().to_u64(), Some(3));
+    }
+
+    #[test]
+    fn test_operands() {
+        let mut packet = parse("1 + (2 > 1)").unwrap();
+        if let Packet::Operator(data) = &mut packet {
+            data.children.push(parse("lt(1, 3)").unwrap());
+        }
+        assert_eq!(interpret(&packet), Ok(3));
+
+        if let Packet::Operator(data) = &mut packet {
+            if let Packet::Operator(data) = &mut data.children[1] {
+                data.children.push(parse("3").unwrap());
+            }
+        }
+        let err = interpret(&packet).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "packet root.1: operator 5 can not take 3 operands"
+        );
+        assert_eq!(interpret_exact(&packet).unwrap_err(), err);
+    }
+
+    #[test]
+    fn test_sum_versions() {
+        // everything is compiled with version 0.
+        assert_eq!(sum_versions(&parse("1 + 2 * 3").unwrap()), 0);
+    }
+}
@@ -0,0 +1,61 @@
+pub mod compiler;
+pub mod decoder;
+pub mod disassembler;
+pub mod encoder;
+pub mod interpreter;
+
+use self::decoder::decode;
+use self::interpreter::{interpret, sum_versions};
+use crate::error::SolveResult;
+
+pub fn try_part_one(input: &str) -> SolveResult<u64> {
+    let packet = decode(input.lines().next().unwrap_or_default())?;
+    Ok(sum_versions(&packet))
+}
+
+pub fn try_part_two(input: &str) -> SolveResult<u64> {
+    let packet = decode(input.lines().next().unwrap_or_default())?;
+    Ok(interpret(&packet)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SolveError;
+    use crate::helpers::parse::ParseError;
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(try_part_one("8A004A801A8002F478"), Ok(16));
+        assert_eq!(try_part_one("A0016C880162017C3686B18A3D4780"), Ok(31));
+        assert_eq!(try_part_one("620080001611562C8802118E34"), Ok(12));
+        assert_eq!(try_part_one("C0015000016115A2E0802F182340"), Ok(23));
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(try_part_two("C200B40A82"), Ok(3));
+        assert_eq!(try_part_two("04005AC33890"), Ok(54));
+        assert_eq!(try_part_two("880086C3E88112"), Ok(7));
+        assert_eq!(try_part_two("CE00C43D881120"), Ok(9));
+        assert_eq!(try_part_two("D8005AC2A8F0"), Ok(1));
+        assert_eq!(try_part_two("F600BC2D8F"), Ok(0));
+        assert_eq!(try_part_two("9C005AC2F8F0"), Ok(0));
+        assert_eq!(try_part_two("9C0141080250320F1802104A08"), Ok(1));
+    }
+
+    #[test]
+    fn test_invalid_messages() {
+        assert!(try_part_one("").is_err());
+        assert!(try_part_one("8A004A801A80").is_err());
+        // invalid digits are reported like any other parse error.
+        assert_eq!(
+            try_part_one("8A004G"),
+            Err(SolveError::Parse(ParseError {
+                line: 1,
+                column: 6,
+                expected: "hexadecimal digit".into()
+            }))
+        );
+    }
+}
@@ -1,8 +1,22 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::{parse_line, Parser};
+use crate::helpers::render::Render;
+use crate::helpers::trace::{notify, Observer};
 use std::cmp::max;
+use std::fmt::{self, Display};
+use std::ops::ControlFlow;
 
 type Point = (isize, isize);
 type Velocity = (isize, isize);
 
+/// the target area needs to be within this distance of the launcher on both axes. every velocity up to it is
+/// tried, so larger targets would take far too long.
+const MAX_DISTANCE: isize = 500;
+
+/// trajectories are scaled down to fit into this many columns and rows.
+const PLOT_COLS: isize = 80;
+const PLOT_ROWS: isize = 40;
+
 struct Bounds {
     left: isize,
     right: isize,
@@ -16,23 +30,101 @@ impl Bounds {
     }
 }
 
-fn parse(line: &str) -> Bounds {
-    let values: Vec<isize> = line
-        .split(',')
-        .flat_map(|part| {
-            part.split('=')
-                .last()
-                .unwrap()
-                .split("..")
-                .map(|x| x.parse().unwrap())
+fn parse(input: &str) -> SolveResult<Bounds> {
+    let line = input.lines().next().unwrap_or_default();
+
+    let bounds = parse_line(line, 1, |p| {
+        p.tag("target area: ")?;
+        let (left, right) = p.key_value("x", Parser::range)?;
+        p.tag(", ")?;
+        let (bottom, top) = p.key_value("y", Parser::range)?;
+
+        Ok(Bounds {
+            left,
+            right,
+            top,
+            bottom,
         })
-        .collect();
+    })?;
+
+    // the search only covers targets that are in front of and below the launcher.
+    if bounds.left < 0
+        || bounds.left > bounds.right
+        || bounds.top >= 0
+        || bounds.bottom > bounds.top
+    {
+        return Err(SolveError::invalid(
+            "target area needs to be in front of and below the launcher",
+        ));
+    }
+
+    if bounds.right > MAX_DISTANCE || bounds.bottom < -MAX_DISTANCE {
+        return Err(SolveError::invalid(format!(
+            "target area needs to be within {} of the launcher",
+            MAX_DISTANCE
+        )));
+    }
+
+    Ok(bounds)
+}
+
+/// A probe after a step of its trajectory, passed to observers.
+pub struct Probe {
+    /// the velocity the probe was launched with.
+    pub launched_with: Velocity,
+    pub position: Point,
+    pub velocity: Velocity,
+    pub hit: bool,
+}
+
+impl Display for Probe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "launched with {:?}: position {:?}, velocity {:?}{}",
+            self.launched_with,
+            self.position,
+            self.velocity,
+            if self.hit { ", hit" } else { "" }
+        )
+    }
+}
+
+/// The path of a single launched probe so far, passed to observers.
+pub struct Trajectory<'a> {
+    pub velocity: Velocity,
+    path: &'a [Point],
+    bounds: &'a Bounds,
+}
 
-    Bounds {
-        left: values[0],
-        right: values[1],
-        bottom: values[2],
-        top: values[3],
+impl Display for Trajectory<'_> {
+    /// plot the path (`#`) from the launcher (`S`) to the target area (`T`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (min_x, min_y) = (0, self.bounds.bottom);
+        let max_x = self.path.iter().map(|p| p.0).fold(self.bounds.right, max);
+        let max_y = self.path.iter().map(|p| p.1).fold(0, max);
+
+        let cols = (max_x - min_x + 1).min(PLOT_COLS);
+        let rows = (max_y - min_y + 1).min(PLOT_ROWS);
+        let col = |x: isize| ((x - min_x) * (cols - 1) / max(max_x - min_x, 1)) as usize;
+        let row = |y: isize| ((max_y - y) * (rows - 1) / max(max_y - min_y, 1)) as usize;
+
+        let mut plot = vec![vec!['.'; cols as usize]; rows as usize];
+        for line in &mut plot[row(self.bounds.top)..=row(self.bounds.bottom)] {
+            line[col(self.bounds.left)..=col(self.bounds.right)].fill('T');
+        }
+        for &(x, y) in self.path {
+            plot[row(y)][col(x)] = '#';
+        }
+        plot[row(0)][col(0)] = 'S';
+
+        write!(
+            f,
+            "{}\nlaunched with {:?}, step {}",
+            plot.render(|c| *c),
+            self.velocity,
+            self.path.len()
+        )
     }
 }
 
@@ -40,7 +132,8 @@ fn simulate_point(
     initial_point: Point,
     initial_velocity: Velocity,
     bounds: &Bounds,
-) -> Option<isize> {
+    observer: &mut impl Observer<Probe>,
+) -> SolveResult<Option<isize>> {
     let mut point = initial_point;
     let mut velocity = initial_velocity;
     let mut y_max = point.1;
@@ -50,39 +143,94 @@ fn simulate_point(
         point = (point.0 + velocity.0, point.1 + velocity.1);
         y_max = max(point.1, y_max);
 
-        if bounds.contains(&point) {
-            return Some(y_max);
+        let hit = bounds.contains(&point);
+        let probe = Probe {
+            launched_with: initial_velocity,
+            position: point,
+            velocity,
+            hit,
+        };
+        notify(observer, &probe)?;
+
+        if hit {
+            return Ok(Some(y_max));
         } else {
             velocity = (max(0, velocity.0 - 1), velocity.1 - 1);
         }
     }
 
-    None
+    Ok(None)
 }
 
-fn find_hits(bounds: &Bounds) -> Vec<isize> {
+/// find all velocities that hit the target area, together with the highest point of their trajectory.
+fn find_hits(
+    bounds: &Bounds,
+    observer: &mut impl Observer<Probe>,
+) -> SolveResult<Vec<(Velocity, isize)>> {
     let mut max_y = Vec::new();
     let initial_position = (0, 0);
 
     for x in 0..=bounds.right {
         for y in bounds.bottom..=-bounds.bottom {
-            if let Some(y) = simulate_point(initial_position, (x, y), bounds) {
-                max_y.push(y);
+            if let Some(y_max) = simulate_point(initial_position, (x, y), bounds, observer)? {
+                max_y.push(((x, y), y_max));
             }
         }
     }
 
-    max_y
+    Ok(max_y)
 }
 
-pub fn part_one(input: &str) -> isize {
-    let bounds = parse(input.lines().next().unwrap());
-    *find_hits(&bounds).iter().max().unwrap()
+fn highest_hit(
+    bounds: &Bounds,
+    observer: &mut impl Observer<Probe>,
+) -> SolveResult<(Velocity, isize)> {
+    find_hits(bounds, observer)?
+        .into_iter()
+        .max_by_key(|(_, y)| *y)
+        .ok_or_else(|| SolveError::no_solution("no velocity hits the target area"))
 }
 
-pub fn part_two(input: &str) -> usize {
-    let bounds = parse(input.lines().next().unwrap());
-    find_hits(&bounds).len()
+/// like `try_part_one`, but `observer` sees every step of every launched probe.
+pub fn observe_part_one(input: &str, observer: &mut impl Observer<Probe>) -> SolveResult<isize> {
+    let bounds = parse(input)?;
+    Ok(highest_hit(&bounds, observer)?.1)
+}
+
+/// like `try_part_one`, but `observer` follows the highest trajectory step by step once it is found.
+pub fn observe_highest_trajectory(
+    input: &str,
+    observer: &mut impl for<'a> Observer<Trajectory<'a>>,
+) -> SolveResult<isize> {
+    let bounds = parse(input)?;
+    let (velocity, y_max) = highest_hit(&bounds, &mut ())?;
+
+    let mut path = Vec::new();
+    let mut record = |probe: &Probe| {
+        path.push(probe.position);
+        ControlFlow::Continue(())
+    };
+    simulate_point((0, 0), velocity, &bounds, &mut record)?;
+
+    for step in 1..=path.len() {
+        let trajectory = Trajectory {
+            velocity,
+            path: &path[..step],
+            bounds: &bounds,
+        };
+        notify(observer, &trajectory)?;
+    }
+
+    Ok(y_max)
+}
+
+pub fn try_part_one(input: &str) -> SolveResult<isize> {
+    observe_part_one(input, &mut ())
+}
+
+pub fn try_part_two(input: &str) -> SolveResult<usize> {
+    let bounds = parse(input)?;
+    Ok(find_hits(&bounds, &mut ())?.len())
 }
 
 #[cfg(test)]
@@ -91,15 +239,72 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 17);
-        assert_eq!(part_one(&input), 45);
+        assert_eq!(try_part_one(&input), Ok(45));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 17);
-        assert_eq!(part_two(&input), 112);
+        assert_eq!(try_part_two(&input), Ok(112));
+    }
+
+    #[test]
+    fn test_invalid_targets() {
+        assert!(try_part_one("target area: x=20..30, y=5..10").is_err());
+        assert_eq!(
+            try_part_one("target area: x=0..1, y=-9223372036854775808..-1"),
+            Err(SolveError::invalid(
+                "target area needs to be within 500 of the launcher"
+            ))
+        );
+        assert!(try_part_two("target area: x=0..1000000000, y=-10..-5").is_err());
+        // the largest accepted target is still searched quickly.
+        assert!(try_part_two("target area: x=490..500, y=-500..-490").is_ok());
+    }
+
+    #[test]
+    fn test_observe() {
+        use crate::read_file;
+        use std::ops::ControlFlow;
+
+        let input = read_file("examples", 17);
+        let mut trajectory = Vec::new();
+        let result = observe_part_one(&input, &mut |probe: &Probe| {
+            if probe.launched_with == (6, 9) {
+                trajectory.push((probe.position, probe.hit));
+            }
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(result, Ok(45));
+        assert_eq!(trajectory.len(), 20);
+        assert_eq!(trajectory[9], ((21, 45), false));
+        assert_eq!(trajectory.last(), Some(&((21, -10), true)));
+    }
+
+    #[test]
+    fn test_observe_highest_trajectory() {
+        use crate::read_file;
+
+        let input = read_file("examples", 17);
+        let mut frames = Vec::new();
+        let result = observe_highest_trajectory(&input, &mut |t: &Trajectory| {
+            frames.push(t.to_string());
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(result, Ok(45));
+        assert_eq!(frames.len(), 20);
+        let last = frames.last().unwrap();
+        assert!(last.ends_with("launched with (7, 9), step 20"));
+        assert!(last.starts_with("............................#..\n"));
+        // the plot is scaled to 40 rows, the probe hits the bottom row of the target area.
+        assert_eq!(
+            last.lines().nth(39),
+            Some("....................TTTTTTTT#TT")
+        );
     }
 }
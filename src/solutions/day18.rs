@@ -1,8 +1,13 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::{parse_line, parse_lines, ParseResult, Parser};
 use itertools::Itertools;
 use std::cmp::max;
 
+/// reduced snail numbers never nest pairs deeper than this.
+const MAX_DEPTH: usize = 4;
+
 #[derive(Clone, Copy)]
-enum Symbol {
+pub(crate) enum Symbol {
     Open,
     Close,
     Comma,
@@ -32,8 +37,9 @@ fn add(a: &[Symbol], b: &[Symbol]) -> Snail {
     snail
 }
 
-fn from_str(s: &str) -> Snail {
-    s.chars().map(Symbol::from_char).collect()
+/// parse a single snail number, rejecting anything that `reduce` can not handle.
+pub(crate) fn from_str(s: &str) -> ParseResult<Snail> {
+    parse_line(s, 1, snail)
 }
 
 fn reduce(snail: &mut Snail) {
@@ -124,8 +130,36 @@ fn explode(snail: &mut Snail) -> bool {
     }
 }
 
-fn parse(input: &str) -> Vec<Snail> {
-    input.lines().map(from_str).collect()
+/// check that the rest of the line starts with a regular number or a pair of elements.
+/// reduction assumes that exploding pairs consist of regular numbers, so inputs may not be nested too deeply.
+fn validate_element(p: &mut Parser, depth: usize) -> ParseResult<()> {
+    if p.try_tag("[") {
+        if depth == MAX_DEPTH {
+            return Err(p.error(format!("pairs nested at most {} levels deep", MAX_DEPTH)));
+        }
+        validate_element(p, depth + 1)?;
+        p.tag(",")?;
+        validate_element(p, depth + 1)?;
+        p.tag("]")
+    } else {
+        p.take_while("digit or `[`", |c| c.is_ascii_digit())
+            .and_then(|digits| match digits.len() {
+                1 => Ok(()),
+                _ => Err(p.error("single digit")),
+            })
+    }
+}
+
+fn snail(p: &mut Parser) -> ParseResult<Snail> {
+    let line = p.rest();
+    validate_element(p, 0)?;
+    // every character needs to be valid to be turned into a symbol, so trailing characters are rejected first.
+    p.end()?;
+    Ok(line.chars().map(Symbol::from_char).collect())
+}
+
+fn parse(input: &str) -> ParseResult<Vec<Snail>> {
+    parse_lines(input, snail)
 }
 
 // this previously used a recursive function based on casting to json.
@@ -146,17 +180,23 @@ fn calc_magnitude(snail: &[Symbol]) -> u32 {
     output
 }
 
-pub fn part_one(input: &str) -> u32 {
-    calc_magnitude(
-        &parse(input)
-            .into_iter()
-            .fold1(|acc, curr| add(&acc, &curr))
-            .unwrap(),
-    )
+pub fn try_part_one(input: &str) -> SolveResult<u32> {
+    let sum = parse(input)?
+        .into_iter()
+        .fold1(|acc, curr| add(&acc, &curr))
+        .ok_or_else(|| SolveError::invalid("no snail numbers"))?;
+
+    Ok(calc_magnitude(&sum))
 }
 
-pub fn part_two(input: &str) -> u32 {
-    parse(input).iter().combinations(2).fold(0, |acc, snails| {
+pub fn try_part_two(input: &str) -> SolveResult<u32> {
+    let snails = parse(input)?;
+
+    if snails.len() < 2 {
+        return Err(SolveError::no_solution("need at least two snail numbers"));
+    }
+
+    Ok(snails.iter().combinations(2).fold(0, |acc, snails| {
         max(
             acc,
             max(
@@ -164,7 +204,103 @@ pub fn part_two(input: &str) -> u32 {
                 calc_magnitude(&add(snails[1], snails[0])),
             ),
         )
-    })
+    }))
+}
+
+/// snail numbers as binary trees, reduced and measured recursively.
+#[cfg(test)]
+mod naive {
+    #[derive(Clone, Debug)]
+    pub enum Tree {
+        Num(u32),
+        Pair(Box<Tree>, Box<Tree>),
+    }
+
+    pub fn parse(line: &str) -> Tree {
+        fn element(chars: &mut std::str::Chars) -> Tree {
+            match chars.next().unwrap() {
+                '[' => {
+                    let left = element(chars);
+                    chars.next();
+                    let right = element(chars);
+                    chars.next();
+                    Tree::Pair(Box::new(left), Box::new(right))
+                }
+                c => Tree::Num(c.to_digit(10).unwrap()),
+            }
+        }
+
+        element(&mut line.chars())
+    }
+
+    fn add_leftmost(tree: &mut Tree, value: u32) {
+        match tree {
+            Tree::Num(x) => *x += value,
+            Tree::Pair(left, _) => add_leftmost(left, value),
+        }
+    }
+
+    fn add_rightmost(tree: &mut Tree, value: u32) {
+        match tree {
+            Tree::Num(x) => *x += value,
+            Tree::Pair(_, right) => add_rightmost(right, value),
+        }
+    }
+
+    /// explode the leftmost pair nested inside four pairs.
+    /// returns the values that still need to be added to the left and right neighbors.
+    fn explode(tree: &mut Tree, depth: usize) -> Option<(u32, u32)> {
+        let Tree::Pair(left, right) = tree else {
+            return None;
+        };
+
+        if depth == 4 {
+            if let (Tree::Num(l), Tree::Num(r)) = (left.as_ref(), right.as_ref()) {
+                let carry = (*l, *r);
+                *tree = Tree::Num(0);
+                return Some(carry);
+            }
+        }
+
+        if let Some((l, r)) = explode(left, depth + 1) {
+            add_leftmost(right, r);
+            return Some((l, 0));
+        }
+
+        if let Some((l, r)) = explode(right, depth + 1) {
+            add_rightmost(left, l);
+            return Some((0, r));
+        }
+
+        None
+    }
+
+    fn split(tree: &mut Tree) -> bool {
+        match tree {
+            Tree::Num(x) if *x > 9 => {
+                *tree = Tree::Pair(
+                    Box::new(Tree::Num(*x / 2)),
+                    Box::new(Tree::Num(*x - *x / 2)),
+                );
+                true
+            }
+            Tree::Num(_) => false,
+            Tree::Pair(left, right) => split(left) || split(right),
+        }
+    }
+
+    pub fn add(a: &Tree, b: &Tree) -> Tree {
+        let mut tree = Tree::Pair(Box::new(a.clone()), Box::new(b.clone()));
+        while explode(&mut tree, 0).is_some() || split(&mut tree) {}
+        tree
+    }
+
+    pub fn magnitude(tree: &Tree) -> u32 {
+        match tree {
+            Tree::Num(x) => *x,
+            Tree::Pair(left, right) => 3 * magnitude(left) + 2 * magnitude(right),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -173,15 +309,124 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 18);
-        assert_eq!(part_one(&input), 4140);
+        assert_eq!(try_part_one(&input), Ok(4140));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 18);
-        assert_eq!(part_two(&input), 3993);
+        assert_eq!(try_part_two(&input), Ok(3993));
+    }
+
+    #[test]
+    fn test_naive() {
+        use crate::generate;
+        use crate::helpers::rng::Rng;
+
+        for seed in 0..10 {
+            let input = generate::day18(&mut Rng::new(seed), 10);
+            let trees: Vec<naive::Tree> = input.lines().map(naive::parse).collect();
+
+            for (line, tree) in input.lines().zip(&trees) {
+                assert_eq!(
+                    calc_magnitude(&from_str(line).unwrap()),
+                    naive::magnitude(tree)
+                );
+            }
+
+            let sum = trees[1..]
+                .iter()
+                .fold(trees[0].clone(), |acc, t| naive::add(&acc, t));
+            assert_eq!(try_part_one(&input), Ok(naive::magnitude(&sum)));
+
+            let largest = trees
+                .iter()
+                .permutations(2)
+                .map(|pair| naive::magnitude(&naive::add(pair[0], pair[1])))
+                .max();
+            assert_eq!(try_part_two(&input).ok(), largest);
+        }
+    }
+
+    #[test]
+    fn test_reduction_properties() {
+        use crate::generate;
+        use crate::helpers::prop::check_with;
+
+        let numbers = |snail: &[Symbol]| -> Vec<u32> {
+            snail
+                .iter()
+                .filter_map(|s| match s {
+                    Symbol::Num(x) => Some(*x),
+                    _ => None,
+                })
+                .collect()
+        };
+        let max_depth = |snail: &[Symbol]| {
+            snail
+                .iter()
+                .scan(0, |depth, s| {
+                    match s {
+                        Symbol::Open => *depth += 1,
+                        Symbol::Close => *depth -= 1,
+                        _ => (),
+                    }
+                    Some(*depth)
+                })
+                .max()
+                .unwrap_or(0)
+        };
+
+        check_with(
+            |rng, _| generate::day18(rng, 2),
+            |_| Vec::new(),
+            |input| {
+                let lines: Vec<Snail> = input.lines().map(|l| from_str(l).unwrap()).collect();
+                let (a, b) = (&lines[0], &lines[1]);
+
+                // the left element weighs more than the right one, so swapping a pair orders by magnitude.
+                let pair = |a: &[Symbol], b: &[Symbol]| {
+                    let mut snail = vec![Symbol::Open];
+                    snail.extend_from_slice(a);
+                    snail.push(Symbol::Comma);
+                    snail.extend_from_slice(b);
+                    snail.push(Symbol::Close);
+                    snail
+                };
+                let mut snail = pair(a, b);
+                let ordered = (calc_magnitude(&snail) >= calc_magnitude(&pair(b, a)))
+                    == (calc_magnitude(a) >= calc_magnitude(b));
+
+                // walk through the reduction one action at a time.
+                let mut steps_hold = true;
+                loop {
+                    let before = snail.clone();
+                    let sum: u32 = numbers(&before).iter().sum();
+
+                    if explode(&mut snail) {
+                        // exploded values move to their neighbours, only values falling off the edges get lost.
+                        let after: u32 = numbers(&snail).iter().sum();
+                        steps_hold &= after <= sum && snail.len() == before.len() - 4;
+                    } else if split(&mut snail) {
+                        // splitting keeps the sum, rounds towards the right and always adds weight.
+                        let (old, new) = (numbers(&before), numbers(&snail));
+                        let i = old.iter().position(|x| *x > 9).unwrap();
+                        steps_hold &= max_depth(&before) <= MAX_DEPTH
+                            && new[i] + new[i + 1] == old[i]
+                            && new[i + 1] - new[i] <= 1
+                            && calc_magnitude(&snail) > calc_magnitude(&before);
+                    } else {
+                        break;
+                    }
+                }
+
+                let reduced =
+                    numbers(&snail).iter().all(|x| *x <= 9) && max_depth(&snail) <= MAX_DEPTH;
+                ordered && steps_hold && reduced
+            },
+        );
     }
 }
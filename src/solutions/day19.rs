@@ -1,11 +1,16 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::{blocks, parse_line, ParseResult, Parser};
 use itertools::Itertools;
 use std::{
     collections::HashSet,
     ops::{Add, Sub},
 };
 
+/// scanners detect beacons at most this many units away on each axis.
+const MAX_RANGE: i32 = 1000;
+
 #[derive(Clone, Copy, Eq, Hash, PartialEq)]
-struct Point(i32, i32, i32);
+pub(crate) struct Point(i32, i32, i32);
 
 impl Add for Point {
     type Output = Self;
@@ -73,23 +78,51 @@ type Reports = Vec<Report>;
 
 type Distances = HashSet<i32>;
 
-fn parse(input: &str) -> Reports {
-    input.lines().fold(Vec::new(), |mut acc, l| {
-        if l.starts_with("---") {
-            acc.push(vec![]);
-        } else if !l.is_empty() {
-            let mut coords = l.split(',').map(|s| s.parse::<i32>().unwrap());
-            let last = acc.len() - 1;
-
-            acc[last].push(Point(
-                coords.next().unwrap(),
-                coords.next().unwrap(),
-                coords.next().unwrap(),
-            ));
-        }
+fn parse_coordinate(p: &mut Parser) -> ParseResult<i32> {
+    let coordinate = p.integer()?;
+
+    if !(-MAX_RANGE..=MAX_RANGE).contains(&coordinate) {
+        return Err(p.error(format!("coordinate within `{}` of the scanner", MAX_RANGE)));
+    }
+
+    Ok(coordinate)
+}
+
+fn parse_point(p: &mut Parser) -> ParseResult<Point> {
+    let x = parse_coordinate(p)?;
+    p.tag(",")?;
+    let y = parse_coordinate(p)?;
+    p.tag(",")?;
+    let z = parse_coordinate(p)?;
+    Ok(Point(x, y, z))
+}
+
+pub(crate) fn parse(input: &str) -> SolveResult<Reports> {
+    let reports = blocks(input)
+        .iter()
+        .map(|block| {
+            let mut lines = block.lines();
+
+            // blocks are never empty.
+            let (line_number, header) = lines.next().unwrap();
+            parse_line(header, line_number, |p| {
+                p.tag("--- scanner ")?;
+                p.integer::<usize>()?;
+                p.tag(" ---")
+            })?;
+
+            lines
+                .map(|(line_number, line)| parse_line(line, line_number, parse_point))
+                .collect::<ParseResult<Report>>()
+        })
+        .collect::<ParseResult<Reports>>()?;
 
-        acc
-    })
+    // empty reports mark scanners that are not aligned yet, see `align`.
+    if reports.is_empty() || reports.iter().any(|r| r.is_empty()) {
+        return Err(SolveError::invalid("every scanner needs to report beacons"));
+    }
+
+    Ok(reports)
 }
 
 fn distances(reports: &[Report]) -> Vec<Distances> {
@@ -121,15 +154,43 @@ fn unaligned_neighbors(neighbors: &[Neighbors], aligned: &[Report]) -> Option<Ne
         .map(|(a, b)| (*a, *b))
 }
 
-fn find_pair_by_distance(reports: &[Point], distance: i32) -> (&Point, &Point) {
+fn pairs_by_distance(reports: &[Point], distance: i32) -> impl Iterator<Item = (&Point, &Point)> {
     reports
         .iter()
         .tuple_combinations()
-        .find(|(a, b)| a.distance(b) == distance)
-        .unwrap()
+        .filter(move |(a, b)| a.distance(b) == distance)
 }
 
-fn align(reports: &[Report]) -> (Vec<Report>, Vec<Point>) {
+/// find the rotation and offset that map at least 12 beacons of `report` onto `reference`.
+/// distances can coincide between unrelated pairs of beacons, so every candidate is checked against all beacons.
+fn find_alignment(reference: &[Point], report: &[Point], common: &[i32]) -> Option<(u8, Point)> {
+    let known: HashSet<Point> = reference.iter().copied().collect();
+
+    for &distance in common {
+        for (c0, c1) in pairs_by_distance(reference, distance) {
+            for (t0, _) in pairs_by_distance(report, distance) {
+                for rot in 0..24 {
+                    let r0 = t0.rotate(rot);
+
+                    for alignment in [*c0 - r0, *c1 - r0] {
+                        let overlap = report
+                            .iter()
+                            .filter(|p| known.contains(&(p.rotate(rot) + alignment)))
+                            .count();
+
+                        if overlap >= 12 {
+                            return Some((rot, alignment));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn align(reports: &[Report]) -> SolveResult<(Vec<Report>, Vec<Point>)> {
     let distances = distances(reports);
     let neighbors = find_neighbors(&distances);
 
@@ -138,62 +199,56 @@ fn align(reports: &[Report]) -> (Vec<Report>, Vec<Point>) {
     aligned[0] = reports[0].clone();
 
     while let Some((a, b)) = unaligned_neighbors(&neighbors, &aligned) {
-        let common_distance = distances[a].intersection(&distances[b]).next().unwrap();
-
-        let (c0, c1) = find_pair_by_distance(&aligned[a], *common_distance);
-        let (t0, t1) = find_pair_by_distance(&reports[b], *common_distance);
-
-        let mut alignment: Option<Point> = None;
-        let mut rot = 0;
-
-        while rot < 24 {
-            let r0 = t0.rotate(rot);
-            let r1 = t1.rotate(rot);
-
-            let saligned = r0 - *c0 == r1 - *c1;
-            let asaligned = r0 - *c1 == r1 - *c0;
-
-            if saligned || asaligned {
-                alignment = if saligned {
-                    Some(*c0 - r0)
-                } else {
-                    Some(*c1 - r0)
-                };
-                break;
-            } else {
-                rot += 1;
+        // sorted, so the result does not depend on the iteration order of the sets.
+        let common: Vec<i32> = distances[a]
+            .intersection(&distances[b])
+            .copied()
+            .sorted()
+            .collect();
+
+        match find_alignment(&aligned[a], &reports[b], &common) {
+            Some((rot, alignment)) => {
+                alignments.push(alignment);
+                aligned[b] = reports[b]
+                    .iter()
+                    .map(|p| p.rotate(rot) + alignment)
+                    .collect();
+            }
+            None => {
+                return Err(SolveError::no_solution(format!(
+                    "could not find a canonical orientation for scanner {}",
+                    b
+                )))
             }
         }
+    }
 
-        if let Some(alignment) = alignment {
-            alignments.push(alignment);
-            aligned[b] = reports[b]
-                .iter()
-                .map(|p| p.rotate(rot) + alignment)
-                .collect();
-        } else {
-            panic!("could not find a canonical orientation for all reports!");
-        }
+    if let Some(i) = aligned.iter().position(|r| r.is_empty()) {
+        return Err(SolveError::no_solution(format!(
+            "scanner {} does not overlap with any aligned scanner",
+            i
+        )));
     }
 
-    (aligned, alignments)
+    Ok((aligned, alignments))
 }
 
-pub fn part_one(input: &str) -> usize {
-    let reports = parse(input);
-    align(&reports).0.iter().flatten().unique().count()
+pub fn try_part_one(input: &str) -> SolveResult<usize> {
+    let reports = parse(input)?;
+    Ok(align(&reports)?.0.iter().flatten().unique().count())
 }
 
-pub fn part_two(input: &str) -> i32 {
-    let reports = parse(input);
-    let (_, alignments) = align(&reports);
+pub fn try_part_two(input: &str) -> SolveResult<i32> {
+    let reports = parse(input)?;
+    let (_, alignments) = align(&reports)?;
 
-    alignments
+    // a single scanner has no distance to any other scanner.
+    Ok(alignments
         .iter()
         .tuple_combinations()
         .map(|(a, b)| a.manhattan_distance(b))
         .max()
-        .unwrap()
+        .unwrap_or(0))
 }
 
 #[cfg(test)]
@@ -202,15 +257,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 19);
-        assert_eq!(part_one(&input), 79);
+        assert_eq!(try_part_one(&input), Ok(79));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 19);
-        assert_eq!(part_two(&input), 3621);
+        assert_eq!(try_part_two(&input), Ok(3621));
     }
 }
@@ -1,20 +1,66 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::{parse_line, parse_rows, sections, ParseError};
+use crate::helpers::render::Render;
+use crate::helpers::trace::{notify, Observer};
+use std::fmt::{self, Display};
+
 type Pixels = Vec<bool>;
 type Grid = Vec<Pixels>;
 
+/// every output pixel is looked up by the 9 bits of its surrounding pixels.
+const CIPHER_LENGTH: usize = 512;
+
 pub fn arr_to_int(bits: &[bool]) -> usize {
     bits.iter().fold(0, |acc, &b| acc * 2 + (b as usize))
 }
 
-fn to_pixels(s: &str) -> Pixels {
-    s.chars().map(|c| c == '#').collect()
+fn to_pixel(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
+}
+
+fn parse(input: &str) -> SolveResult<(Pixels, Grid)> {
+    let [cipher, image] = sections(input)?;
+
+    let cipher: Pixels = parse_line(cipher.text, cipher.first_line, |p| {
+        p.take_while("`#` or `.`", |c| to_pixel(c).is_some())
+            .map(|s| s.chars().filter_map(to_pixel).collect())
+    })?;
+
+    if cipher.len() != CIPHER_LENGTH {
+        return Err(SolveError::invalid(format!(
+            "cipher needs to have {} pixels, found {}",
+            CIPHER_LENGTH,
+            cipher.len()
+        )));
+    }
+
+    // `parse_rows` counts lines from the start of the image.
+    let grid = parse_rows(image.text, "`#` or `.`", to_pixel).map_err(|e| ParseError {
+        line: e.line + image.first_line - 1,
+        ..e
+    })?;
+
+    Ok((cipher, grid))
 }
 
-fn parse(input: &str) -> (Pixels, Grid) {
-    let mut lines = input.lines();
-    let cipher = to_pixels(lines.next().unwrap());
-    let grid = lines.filter(|l| !l.is_empty()).map(to_pixels).collect();
+/// The image after an enhancement, passed to observers.
+pub struct Image<'a> {
+    pub step: u32,
+    /// whether the infinite area around the image is lit.
+    pub background: bool,
+    pixels: &'a [Pixels],
+}
 
-    (cipher, grid)
+impl Display for Image<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rendered = self.pixels.render(|&x| if x { '#' } else { '.' });
+        let background = if self.background { "lit" } else { "dark" };
+        write!(f, "{}\nbackground: {}", rendered, background)
+    }
 }
 
 fn pad(grid: &mut Vec<Pixels>, state: bool) {
@@ -62,27 +108,54 @@ fn expand(grid: &mut Vec<Pixels>, cipher: &[bool], state: bool) -> bool {
     cipher[arr_to_int(&[state; 9])]
 }
 
-fn expand_times(input: &str, times: u32) -> Vec<Pixels> {
-    let (cipher, mut grid) = parse(input);
+fn expand_times(
+    input: &str,
+    times: u32,
+    observer: &mut impl for<'a> Observer<Image<'a>>,
+) -> SolveResult<Vec<Pixels>> {
+    let (cipher, mut grid) = parse(input)?;
     let mut state = false;
 
-    for _ in 0..times {
+    for step in 1..=times {
         state = expand(&mut grid, &cipher, state);
+
+        let image = Image {
+            step,
+            background: state,
+            pixels: &grid,
+        };
+        notify(observer, &image)?;
     }
 
-    grid
+    Ok(grid)
 }
 
 fn count(arr: &[Pixels]) -> usize {
     arr.iter().flatten().filter(|&&x| x).count()
 }
 
-pub fn part_one(input: &str) -> usize {
-    count(&expand_times(input, 2))
+/// like `try_part_one`, but `observer` sees the image after every enhancement.
+pub fn observe_part_one(
+    input: &str,
+    observer: &mut impl for<'a> Observer<Image<'a>>,
+) -> SolveResult<usize> {
+    Ok(count(&expand_times(input, 2, observer)?))
 }
 
-pub fn part_two(input: &str) -> usize {
-    count(&expand_times(input, 50))
+pub fn try_part_one(input: &str) -> SolveResult<usize> {
+    observe_part_one(input, &mut ())
+}
+
+/// like `try_part_two`, but `observer` sees the image after every enhancement.
+pub fn observe_part_two(
+    input: &str,
+    observer: &mut impl for<'a> Observer<Image<'a>>,
+) -> SolveResult<usize> {
+    Ok(count(&expand_times(input, 50, observer)?))
+}
+
+pub fn try_part_two(input: &str) -> SolveResult<usize> {
+    observe_part_two(input, &mut ())
 }
 
 #[cfg(test)]
@@ -91,15 +164,50 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 20);
-        assert_eq!(part_one(&input), 35);
+        assert_eq!(try_part_one(&input), Ok(35));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
+        let input = read_file("examples", 20);
+        assert_eq!(try_part_two(&input), Ok(3351));
+    }
+
+    #[test]
+    fn test_snapshot() {
+        use crate::helpers::snapshot::assert_snapshot;
+        use crate::read_file;
+        use std::fmt::Write;
+
+        let (cipher, mut grid) = parse(&read_file("examples", 20)).unwrap();
+        let render = |grid: &Grid| grid.render(|&x| if x { '#' } else { '.' });
+        let mut states = format!("input image:\n{}\n\n", render(&grid));
+        let mut state = false;
+
+        for step in 1..=2 {
+            state = expand(&mut grid, &cipher, state);
+            write!(states, "after enhancement {}:\n{}\n\n", step, render(&grid)).unwrap();
+        }
+
+        assert_snapshot("day20", &states);
+    }
+
+    #[test]
+    fn test_observe() {
+        use crate::read_file;
+        use std::ops::ControlFlow;
+
         let input = read_file("examples", 20);
-        assert_eq!(part_two(&input), 3351);
+        let mut lit = Vec::new();
+        let result = observe_part_one(&input, &mut |image: &Image| {
+            lit.push((image.step, count(image.pixels), image.background));
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(result, Ok(35));
+        assert_eq!(lit, vec![(1, 24, false), (2, 35, false)]);
     }
 }
@@ -1,11 +1,49 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::parse_lines;
+use crate::helpers::trace::{notify, Observer};
 use std::collections::HashMap;
+use std::fmt::{self, Display};
 
-fn parse(input: &str) -> Vec<u64> {
-    input
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|l| l.split(": ").last().unwrap().parse::<u64>().unwrap())
-        .collect()
+fn parse(input: &str) -> SolveResult<(u64, u64)> {
+    let positions = parse_lines(input, |p| {
+        p.tag("Player ")?;
+        p.integer::<u8>()?;
+        p.tag(" starting position: ")?;
+        let position = p.integer()?;
+
+        if (1..=10).contains(&position) {
+            Ok(position)
+        } else {
+            Err(p.error("position between 1 and 10"))
+        }
+    })?;
+
+    match positions.as_slice() {
+        [p1, p2] => Ok((*p1, *p2)),
+        _ => Err(SolveError::invalid(format!(
+            "expected 2 players, found {}",
+            positions.len()
+        ))),
+    }
+}
+
+/// The game after a player's turn with the deterministic die, passed to observers.
+pub struct Turn {
+    /// the player who just moved, `1` or `2`.
+    pub player: u8,
+    pub rolls: u64,
+    pub positions: [u64; 2],
+    pub scores: [u64; 2],
+}
+
+impl Display for Turn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "player {} moved after {} rolls: positions {:?}, scores {:?}",
+            self.player, self.rolls, self.positions, self.scores
+        )
+    }
 }
 
 fn deterministic_roll(position: &mut u64, score: &mut u64, dice: &mut u64) {
@@ -16,27 +54,41 @@ fn deterministic_roll(position: &mut u64, score: &mut u64, dice: &mut u64) {
     *dice += 3;
 }
 
-pub fn part_one(input: &str) -> u64 {
-    let positions = parse(input);
-    let mut p1_position = positions[0];
-    let mut p2_position = positions[1];
+/// like `try_part_one`, but `observer` sees the game after every turn.
+pub fn observe_part_one(input: &str, observer: &mut impl Observer<Turn>) -> SolveResult<u64> {
+    let (mut p1_position, mut p2_position) = parse(input)?;
     let mut p1_score = 0;
     let mut p2_score = 0;
     let mut dice = 1;
 
+    let turn = |player, dice: u64, positions, scores| Turn {
+        player,
+        rolls: dice - 1,
+        positions,
+        scores,
+    };
+
     loop {
         deterministic_roll(&mut p1_position, &mut p1_score, &mut dice);
+        let state = turn(1, dice, [p1_position, p2_position], [p1_score, p2_score]);
+        notify(observer, &state)?;
         if p1_score >= 1000 {
-            return p2_score * (dice - 1);
+            return Ok(p2_score * (dice - 1));
         }
 
         deterministic_roll(&mut p2_position, &mut p2_score, &mut dice);
+        let state = turn(2, dice, [p1_position, p2_position], [p1_score, p2_score]);
+        notify(observer, &state)?;
         if p2_score >= 1000 {
-            return p1_score * (dice - 1);
+            return Ok(p1_score * (dice - 1));
         }
     }
 }
 
+pub fn try_part_one(input: &str) -> SolveResult<u64> {
+    observe_part_one(input, &mut ())
+}
+
 // possible rolls for a 3-sided die.
 static ROLLS: [(u64, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
 
@@ -76,15 +128,77 @@ fn play(
     }
 }
 
-pub fn part_two(input: &str) -> u64 {
+pub fn try_part_two(input: &str) -> SolveResult<u64> {
     let mut cache = HashMap::new();
 
-    let positions = parse(input);
-    let p1_position = positions[0];
-    let p2_position = positions[1];
+    let (p1_position, p2_position) = parse(input)?;
 
     let (p1_wins, p2_wins) = play(p1_position, p2_position, 0, 0, &mut cache);
-    std::cmp::max(p1_wins, p2_wins)
+    Ok(std::cmp::max(p1_wins, p2_wins))
+}
+
+/// plays the games turn by turn without any shortcuts.
+#[cfg(test)]
+mod naive {
+    use std::collections::HashMap;
+
+    /// roll a wrapping 100-sided die, keeping track of every single roll.
+    pub fn deterministic(positions: [u64; 2]) -> u64 {
+        let mut positions = positions;
+        let mut scores = [0, 0];
+        let mut die = 0;
+        let mut rolls = 0;
+
+        for player in [0, 1].into_iter().cycle() {
+            for _ in 0..3 {
+                die = die % 100 + 1;
+                rolls += 1;
+                positions[player] = (positions[player] + die - 1) % 10 + 1;
+            }
+            scores[player] += positions[player];
+
+            if scores[player] >= 1000 {
+                return scores[1 - player] * rolls;
+            }
+        }
+
+        unreachable!()
+    }
+
+    /// count universes turn by turn, splitting every universe into 27 for the three rolls of a turn.
+    pub fn dirac(positions: [u64; 2]) -> u64 {
+        let mut universes: HashMap<([u64; 2], [u64; 2]), u64> =
+            HashMap::from([((positions, [0, 0]), 1)]);
+        let mut wins = [0, 0];
+
+        for player in [0, 1].into_iter().cycle() {
+            if universes.is_empty() {
+                break;
+            }
+
+            let mut next = HashMap::new();
+
+            for ((positions, scores), count) in universes {
+                for rolls in 0..27 {
+                    let roll = 3 + rolls % 3 + rolls / 3 % 3 + rolls / 9;
+                    let mut positions = positions;
+                    let mut scores = scores;
+                    positions[player] = (positions[player] + roll - 1) % 10 + 1;
+                    scores[player] += positions[player];
+
+                    if scores[player] >= 21 {
+                        wins[player] += count;
+                    } else {
+                        *next.entry((positions, scores)).or_default() += count;
+                    }
+                }
+            }
+
+            universes = next;
+        }
+
+        wins[0].max(wins[1])
+    }
 }
 
 #[cfg(test)]
@@ -93,15 +207,55 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 21);
-        assert_eq!(part_one(&input), 739785);
+        assert_eq!(try_part_one(&input), Ok(739785));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
+        let input = read_file("examples", 21);
+        assert_eq!(try_part_two(&input), Ok(444356092776315));
+    }
+
+    #[test]
+    fn test_naive() {
+        let input = |p1, p2| {
+            format!(
+                "Player 1 starting position: {}\nPlayer 2 starting position: {}\n",
+                p1, p2
+            )
+        };
+
+        for p1 in 1..=10 {
+            for p2 in 1..=10 {
+                let input = input(p1, p2);
+                assert_eq!(try_part_one(&input), Ok(naive::deterministic([p1, p2])));
+            }
+        }
+
+        // counting every universe is slow, only check a few starting positions.
+        for (p1, p2) in [(1, 1), (3, 9), (10, 2)] {
+            assert_eq!(try_part_two(&input(p1, p2)), Ok(naive::dirac([p1, p2])));
+        }
+    }
+
+    #[test]
+    fn test_observe() {
+        use crate::read_file;
+        use std::ops::ControlFlow;
+
         let input = read_file("examples", 21);
-        assert_eq!(part_two(&input), 444356092776315);
+        let mut turns = Vec::new();
+        let result = observe_part_one(&input, &mut |turn: &Turn| {
+            turns.push((turn.player, turn.positions, turn.scores));
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(result, Ok(739785));
+        assert_eq!(turns.len(), 331);
+        assert_eq!(turns[0], (1, [10, 8], [10, 0]));
+        assert_eq!(turns[1], (2, [10, 3], [10, 3]));
     }
 }
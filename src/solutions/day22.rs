@@ -1,3 +1,5 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::{parse_lines, ParseResult, Parser};
 use std::cmp::{max, min};
 
 #[derive(Clone)]
@@ -14,34 +16,41 @@ struct Ranges {
 }
 
 #[derive(Clone)]
-struct Cube {
+pub(crate) struct Cube {
     on: bool,
     ranges: Ranges,
 }
 
-fn parse_range(s: &str) -> Range {
-    let mut range = s.split('=').last().unwrap().split("..");
-    let from = range.next().unwrap().parse().unwrap();
-    let to = range.next().unwrap().parse().unwrap();
+fn parse_range(p: &mut Parser, axis: &str) -> ParseResult<Range> {
+    let (from, to) = p.key_value(axis, Parser::range)?;
 
-    Range { from, to }
-}
-
-fn parse(input: &str) -> Vec<Cube> {
-    input
-        .lines()
-        .map(|l| {
-            let on = l.starts_with("on");
-            let mut ranges = l.split(' ').last().unwrap().split(',').map(parse_range);
+    if from > to {
+        return Err(p.error(format!("`{}` range to be ascending", axis)));
+    }
 
-            let x = ranges.next().unwrap();
-            let y = ranges.next().unwrap();
-            let z = ranges.next().unwrap();
-            let ranges = Ranges { x, y, z };
+    Ok(Range { from, to })
+}
 
-            Cube { on, ranges }
-        })
-        .collect()
+pub(crate) fn parse(input: &str) -> ParseResult<Vec<Cube>> {
+    parse_lines(input, |p| {
+        let on = if p.try_tag("on") {
+            true
+        } else if p.try_tag("off") {
+            false
+        } else {
+            return Err(p.error("`on` or `off`"));
+        };
+
+        p.tag(" ")?;
+        let x = parse_range(p, "x")?;
+        p.tag(",")?;
+        let y = parse_range(p, "y")?;
+        p.tag(",")?;
+        let z = parse_range(p, "z")?;
+        let ranges = Ranges { x, y, z };
+
+        Ok(Cube { on, ranges })
+    })
 }
 
 fn intersect(a: &Range, b: &Range) -> Option<Range> {
@@ -81,16 +90,27 @@ fn cube_diffs(instructions: Vec<Cube>) -> Vec<Cube> {
     })
 }
 
-fn vol(r: Range) -> i64 {
-    r.to - r.from + 1
+fn vol(r: Range) -> Option<i64> {
+    r.to.checked_sub(r.from)?.checked_add(1)
 }
 
-fn volume(c: Cube) -> i64 {
+/// signed volume of a cube, `None` if it does not fit into an `i64`.
+fn volume(c: Cube) -> Option<i64> {
     let sign = if c.on { 1 } else { -1 };
-    sign * vol(c.ranges.x) * vol(c.ranges.y) * vol(c.ranges.z)
+    vol(c.ranges.x)?
+        .checked_mul(vol(c.ranges.y)?)?
+        .checked_mul(vol(c.ranges.z)?)?
+        .checked_mul(sign)
+}
+
+fn total_volume(cubes: impl Iterator<Item = Cube>) -> SolveResult<i64> {
+    cubes
+        .map(volume)
+        .try_fold(0i64, |acc, v| acc.checked_add(v?))
+        .ok_or_else(|| SolveError::invalid("reactor volume does not fit into 64 bits"))
 }
 
-pub fn part_one(input: &str) -> i64 {
+pub fn try_part_one(input: &str) -> SolveResult<i64> {
     let bounds = Cube {
         on: true,
         ranges: Ranges {
@@ -100,15 +120,43 @@ pub fn part_one(input: &str) -> i64 {
         },
     };
 
-    cube_diffs(parse(input))
-        .into_iter()
-        .filter_map(|c| intersection(&c, &bounds, c.on))
-        .map(volume)
-        .sum()
+    total_volume(
+        cube_diffs(parse(input)?)
+            .into_iter()
+            .filter_map(|c| intersection(&c, &bounds, c.on)),
+    )
+}
+
+pub fn try_part_two(input: &str) -> SolveResult<i64> {
+    total_volume(cube_diffs(parse(input)?).into_iter())
 }
 
-pub fn part_two(input: &str) -> i64 {
-    cube_diffs(parse(input)).into_iter().map(volume).sum()
+/// switches every single cube, only feasible for small reactors.
+#[cfg(test)]
+mod naive {
+    use super::Cube;
+    use std::collections::HashSet;
+
+    pub fn count_on(steps: &[Cube]) -> usize {
+        let mut on: HashSet<(i64, i64, i64)> = HashSet::new();
+
+        for step in steps {
+            let r = &step.ranges;
+            for x in r.x.from..=r.x.to {
+                for y in r.y.from..=r.y.to {
+                    for z in r.z.from..=r.z.to {
+                        if step.on {
+                            on.insert((x, y, z));
+                        } else {
+                            on.remove(&(x, y, z));
+                        }
+                    }
+                }
+            }
+        }
+
+        on.len()
+    }
 }
 
 #[cfg(test)]
@@ -117,15 +165,43 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 22);
-        assert_eq!(part_one(&input), 474140);
+        assert_eq!(try_part_one(&input), Ok(474140));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 22);
-        assert_eq!(part_two(&input), 2758514936282235);
+        assert_eq!(try_part_two(&input), Ok(2758514936282235));
+    }
+
+    #[test]
+    fn test_naive() {
+        use crate::helpers::rng::Rng;
+        use itertools::Itertools;
+
+        let mut rng = Rng::new(22);
+
+        for _ in 0..20 {
+            let input: String = (0..12)
+                .map(|_| {
+                    let ranges = ["x", "y", "z"]
+                        .iter()
+                        .map(|axis| {
+                            let from = rng.range(-10..=10);
+                            format!("{}={}..{}", axis, from, from + rng.range(0..=8))
+                        })
+                        .join(",");
+                    let state = if rng.chance(0.6) { "on" } else { "off" };
+                    format!("{} {}\n", state, ranges)
+                })
+                .collect();
+
+            let expected = naive::count_on(&parse(&input).unwrap()) as i64;
+            assert_eq!(try_part_one(&input), Ok(expected));
+            assert_eq!(try_part_two(&input), Ok(expected));
+        }
     }
 }
@@ -1,28 +1,790 @@
-/// Solved today by hand on a whiteboard with my family.
-/// This file contains a helper to sum moves and the correct solutions for my input.
-/// Here are some pictures of the whiteboard:
-/// [#1](https://user-images.githubusercontent.com/1682504/147255802-bf21c955-7a1f-412f-9cb0-05627d359635.jpeg)
-/// [#2](https://user-images.githubusercontent.com/1682504/147255905-00f1ac8a-3d5b-4c01-b310-a1a2655a77f4.jpeg)
+//! Originally solved by hand on a whiteboard with my family, see the pictures:
+//! [#1](https://user-images.githubusercontent.com/1682504/147255802-bf21c955-7a1f-412f-9cb0-05627d359635.jpeg)
+//! [#2](https://user-images.githubusercontent.com/1682504/147255905-00f1ac8a-3d5b-4c01-b310-a1a2655a77f4.jpeg)
+//! The solver below searches the burrow with A*, every state is a move of a single amphipod.
+//! `plan_part_one` and `plan_part_two` return the moves of an optimal solution, `Plan::render` replays them
+//! as diagrams to compare against a solution found by hand.
 
-fn sum(l: &str, factor: u64) -> u64 {
-    l.split(' ')
-        .map(|x| x.parse::<u64>().unwrap() * factor)
-        .sum()
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::{parse_line, ParseResult, Parser};
+use crate::helpers::trace::{notify, Observer};
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt::{self, Display};
+
+const HALLWAY: usize = 11;
+const ROOMS: usize = 4;
+const MAX_DEPTH: usize = 4;
+const CELLS: usize = HALLWAY + ROOMS * MAX_DEPTH;
+
+/// energy per step, indexed by kind.
+const ENERGY: [u64; ROOMS] = [1, 10, 100, 1000];
+
+/// hallway cells amphipods can stop on, i.e. all but the ones directly outside a room.
+const STOPS: [usize; 7] = [0, 1, 3, 5, 7, 9, 10];
+
+/// the rows that are folded away in part two, as kinds (`D#C#B#A` and `D#B#A#C`).
+const UNFOLDED: [[u8; ROOMS]; 2] = [[4, 3, 2, 1], [4, 2, 1, 3]];
+
+/// hallway cell in front of `room`.
+fn entrance(room: usize) -> usize {
+    2 + 2 * room
+}
+
+/// A cell of the burrow. Hallway cells are counted from the left, rooms from the left and their rows from the
+/// top, all starting at `0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Hallway(usize),
+    Room { room: usize, row: usize },
+}
+
+impl Cell {
+    fn from_index(index: usize) -> Self {
+        if index < HALLWAY {
+            Cell::Hallway(index)
+        } else {
+            Cell::Room {
+                room: (index - HALLWAY) / MAX_DEPTH,
+                row: (index - HALLWAY) % MAX_DEPTH,
+            }
+        }
+    }
+
+    fn index(&self) -> usize {
+        match *self {
+            Cell::Hallway(x) => x,
+            Cell::Room { room, row } => Burrow::room_cell(room, row),
+        }
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Cell::Hallway(x) => write!(f, "hallway {}", x),
+            Cell::Room { room, row } => write!(f, "room {} row {}", room, row),
+        }
+    }
+}
+
+/// A single amphipod moving from one cell to another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub amphipod: char,
+    pub from: Cell,
+    pub to: Cell,
+    pub energy: u64,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} ({} energy)",
+            self.amphipod, self.from, self.to, self.energy
+        )
+    }
+}
+
+/// An arrangement of amphipods. Cells hold `0` if empty and `1` to `4` for amphipods `A` to `D`.
+/// The first 11 cells are the hallway, followed by the rooms from left to right, each from top to bottom.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Burrow {
+    depth: usize,
+    cells: [u8; CELLS],
+}
+
+impl Burrow {
+    fn room_cell(room: usize, row: usize) -> usize {
+        HALLWAY + room * MAX_DEPTH + row
+    }
+
+    fn room(&self, room: usize) -> &[u8] {
+        let start = Burrow::room_cell(room, 0);
+        &self.cells[start..start + self.depth]
+    }
+
+    /// pack every cell into 3 bits, so states can be stored as compact keys while searching.
+    fn encode(&self) -> u128 {
+        self.cells
+            .iter()
+            .rev()
+            .fold(0, |acc, &cell| (acc << 3) | cell as u128)
+    }
+
+    fn decode(mut key: u128, depth: usize) -> Self {
+        let mut cells = [0; CELLS];
+        for cell in cells.iter_mut() {
+            *cell = (key & 0b111) as u8;
+            key >>= 3;
+        }
+        Burrow { depth, cells }
+    }
+
+    /// a room is finished with if it holds only amphipods that belong there, they never need to move again.
+    fn is_settled(&self, room: usize) -> bool {
+        self.room(room)
+            .iter()
+            .all(|&cell| cell == 0 || cell as usize == room + 1)
+    }
+
+    fn is_organized(&self) -> bool {
+        (0..ROOMS).all(|room| {
+            self.room(room)
+                .iter()
+                .all(|&cell| cell as usize == room + 1)
+        })
+    }
+
+    /// check that all hallway cells between `from` (exclusive) and `to` (inclusive) are empty.
+    fn is_clear(&self, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..to + 1
+        } else {
+            to..from
+        };
+        self.cells[range].iter().all(|&cell| cell == 0)
+    }
+
+    /// insert the two folded rows between the first and the second row of every room.
+    fn unfold(&self) -> Self {
+        let mut unfolded = Burrow {
+            depth: self.depth + UNFOLDED.len(),
+            cells: self.cells,
+        };
+
+        for room in 0..ROOMS {
+            let mut column = vec![self.room(room)[0]];
+            column.extend(UNFOLDED.iter().map(|row| row[room]));
+            column.extend(&self.room(room)[1..]);
+            for (row, &cell) in column.iter().enumerate() {
+                unfolded.cells[Burrow::room_cell(room, row)] = cell;
+            }
+        }
+
+        unfolded
+    }
+
+    /// a lower bound for the energy that is still needed: every amphipod that has to move walks straight to the top
+    /// of its room.
+    fn estimate(&self) -> u64 {
+        let mut energy = 0;
+
+        for (position, &cell) in self.cells[..HALLWAY].iter().enumerate() {
+            if cell != 0 {
+                let kind = cell as usize - 1;
+                energy += (position.abs_diff(entrance(kind)) as u64 + 1) * ENERGY[kind];
+            }
+        }
+
+        for room in 0..ROOMS {
+            // amphipods in the wrong room, or above one that is, need to step aside at least once. the ones
+            // below every stranger of their own room never move again.
+            let mut stranger_below = false;
+            for (row, &cell) in self.room(room).iter().enumerate().rev() {
+                if cell == 0 {
+                    continue;
+                }
+
+                let kind = cell as usize - 1;
+                stranger_below |= kind != room;
+                if stranger_below {
+                    let distance = entrance(room).abs_diff(entrance(kind)).max(2);
+                    energy += (row + 1 + distance + 1) as u64 * ENERGY[kind];
+                }
+            }
+        }
+
+        energy
+    }
+
+    /// move the amphipod in cell `from` to the empty cell `to`, taking `steps` steps.
+    fn step(&self, from: usize, to: usize, steps: usize) -> (Burrow, Move) {
+        let cell = self.cells[from];
+        let mut next = *self;
+        next.cells[from] = 0;
+        next.cells[to] = cell;
+
+        (
+            next,
+            Move {
+                amphipod: (b'A' + cell - 1) as char,
+                from: Cell::from_index(from),
+                to: Cell::from_index(to),
+                energy: steps as u64 * ENERGY[cell as usize - 1],
+            },
+        )
+    }
+
+    /// every legal move of a single amphipod and the arrangement after it.
+    fn moves(&self) -> Vec<(Burrow, Move)> {
+        let mut moves = Vec::new();
+
+        // amphipods in the hallway can only move into their own room, once it holds no strangers.
+        for from in STOPS {
+            let cell = self.cells[from];
+            if cell == 0 {
+                continue;
+            }
+
+            let kind = cell as usize - 1;
+            if !self.is_settled(kind) || !self.is_clear(from, entrance(kind)) {
+                continue;
+            }
+
+            let row = self.room(kind).iter().rposition(|&cell| cell == 0).unwrap();
+            let steps = from.abs_diff(entrance(kind)) + row + 1;
+            moves.push(self.step(from, Burrow::room_cell(kind, row), steps));
+        }
+
+        // the topmost amphipod of a room that still holds strangers can move to any reachable hallway stop,
+        // or straight into its own room. the latter costs the same as stopping on the way, but reads like a plan
+        // made by hand.
+        for room in (0..ROOMS).filter(|&room| !self.is_settled(room)) {
+            let row = self.room(room).iter().position(|&cell| cell != 0).unwrap();
+            let from = Burrow::room_cell(room, row);
+            let kind = self.cells[from] as usize - 1;
+
+            if kind != room
+                && self.is_settled(kind)
+                && self.is_clear(entrance(room), entrance(kind))
+            {
+                let target = self.room(kind).iter().rposition(|&cell| cell == 0).unwrap();
+                let steps = row + 1 + entrance(room).abs_diff(entrance(kind)) + target + 1;
+                moves.push(self.step(from, Burrow::room_cell(kind, target), steps));
+            }
+
+            for to in STOPS {
+                if self.is_clear(entrance(room), to) {
+                    let steps = row + 1 + entrance(room).abs_diff(to);
+                    moves.push(self.step(from, to, steps));
+                }
+            }
+        }
+
+        moves
+    }
+}
+
+impl Display for Burrow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = |cell: u8| match cell {
+            0 => '.',
+            _ => (b'A' + cell - 1) as char,
+        };
+        let row = |row: usize| -> String {
+            (0..ROOMS)
+                .map(|room| symbol(self.room(room)[row]).to_string())
+                .collect::<Vec<_>>()
+                .join("#")
+        };
+
+        writeln!(f, "#############")?;
+        let hallway: String = self.cells[..HALLWAY]
+            .iter()
+            .map(|&cell| symbol(cell))
+            .collect();
+        writeln!(f, "#{}#", hallway)?;
+        writeln!(f, "###{}###", row(0))?;
+        for r in 1..self.depth {
+            writeln!(f, "  #{}#", row(r))?;
+        }
+        write!(f, "  #########")
+    }
+}
+
+/// The cheapest way to organize a burrow, as the moves in the order they are made.
+pub struct Plan {
+    start: Burrow,
+    pub moves: Vec<Move>,
+}
+
+impl Plan {
+    pub fn energy(&self) -> u64 {
+        self.moves.iter().map(|m| m.energy).sum()
+    }
+
+    /// the arrangement after every move, starting with the initial one.
+    fn arrangements(&self) -> Vec<Burrow> {
+        let mut arrangements = vec![self.start];
+
+        for m in &self.moves {
+            let last = arrangements.last().unwrap();
+            arrangements.push(last.step(m.from.index(), m.to.index(), 0).0);
+        }
+
+        arrangements
+    }
+
+    /// Replay the plan as a burrow diagram after every move.
+    pub fn render(&self) -> String {
+        let arrangements = self.arrangements();
+        let mut out = format!("initial arrangement:\n{}\n", arrangements[0]);
+
+        for (i, (m, burrow)) in self.moves.iter().zip(&arrangements[1..]).enumerate() {
+            out += &format!("\nmove {}:\n{}\n{}\n", i + 1, burrow, m);
+        }
+
+        out + &format!("\ntotal energy: {}\n", self.energy())
+    }
+}
+
+/// The burrow after a move of an optimal plan, passed to observers.
+pub struct Replay<'a> {
+    pub step: usize,
+    pub last_move: &'a Move,
+    burrow: &'a Burrow,
+}
+
+impl Display for Replay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\n{}", self.burrow, self.last_move)
+    }
+}
+
+fn amphipod(p: &mut Parser) -> ParseResult<u8> {
+    for (kind, tag) in ["A", "B", "C", "D"].iter().enumerate() {
+        if p.try_tag(tag) {
+            return Ok(kind as u8 + 1);
+        }
+    }
+
+    Err(p.error("amphipod `A`, `B`, `C` or `D`"))
+}
+
+/// parse a row of the rooms, e.g. `###B#C#B#D###` for the first row and `  #A#D#C#A#` for the others.
+fn room_row(p: &mut Parser, first: bool) -> ParseResult<[u8; ROOMS]> {
+    p.tag(if first { "##" } else { "  " })?;
+
+    let mut row = [0; ROOMS];
+    for cell in row.iter_mut() {
+        p.tag("#")?;
+        *cell = amphipod(p)?;
+    }
+
+    p.tag(if first { "###" } else { "#" })?;
+    Ok(row)
 }
 
-fn add_lines(pink: &str, blue: &str, green: &str, purple: &str) -> u64 {
-    sum(pink, 1) + sum(blue, 10) + sum(green, 100) + sum(purple, 1000)
+fn parse(input: &str) -> SolveResult<Burrow> {
+    let lines: Vec<&str> = input.lines().collect();
+    let depth = lines.len().saturating_sub(3);
+
+    if !(1..=MAX_DEPTH).contains(&depth) {
+        return Err(SolveError::invalid(format!(
+            "expected a burrow with 1 to {} rows of rooms, found {} lines",
+            MAX_DEPTH,
+            lines.len()
+        )));
+    }
+
+    parse_line(lines[0], 1, |p| p.tag("#############"))?;
+    parse_line(lines[1], 2, |p| p.tag("#...........#"))?;
+
+    let mut burrow = Burrow {
+        depth,
+        cells: [0; CELLS],
+    };
+
+    for row in 0..depth {
+        let cells = parse_line(lines[row + 2], row + 3, |p| room_row(p, row == 0))?;
+        for (room, &cell) in cells.iter().enumerate() {
+            burrow.cells[Burrow::room_cell(room, row)] = cell;
+        }
+    }
+
+    parse_line(lines[depth + 2], depth + 3, |p| p.tag("  #########"))?;
+
+    for kind in 1..=ROOMS as u8 {
+        if burrow.cells.iter().filter(|&&cell| cell == kind).count() != depth {
+            return Err(SolveError::invalid(format!(
+                "expected {} amphipods of every kind",
+                depth
+            )));
+        }
+    }
+
+    Ok(burrow)
+}
+
+/// find the moves that organize `burrow` with the least energy, using A*.
+fn organize(burrow: &Burrow) -> SolveResult<Plan> {
+    let mut energy: HashMap<u128, u64> = HashMap::new();
+    // the arrangement before and the move leading to every reached arrangement.
+    let mut previous: HashMap<u128, (u128, Move)> = HashMap::new();
+    let mut frontier = BinaryHeap::new();
+
+    energy.insert(burrow.encode(), 0);
+    frontier.push(Reverse((burrow.estimate(), 0, burrow.encode())));
+
+    while let Some(Reverse((_, cost, key))) = frontier.pop() {
+        // skip: there is a cheaper way to this state already.
+        if cost > energy[&key] {
+            continue;
+        }
+
+        let current = Burrow::decode(key, burrow.depth);
+        if current.is_organized() {
+            let mut moves = Vec::new();
+            let mut key = key;
+            while let Some(&(before, m)) = previous.get(&key) {
+                moves.push(m);
+                key = before;
+            }
+            moves.reverse();
+
+            return Ok(Plan {
+                start: *burrow,
+                moves,
+            });
+        }
+
+        for (next, m) in current.moves() {
+            let next_cost = cost + m.energy;
+            let next_key = next.encode();
+
+            let improved = match energy.entry(next_key) {
+                Entry::Occupied(mut entry) if next_cost < *entry.get() => {
+                    entry.insert(next_cost);
+                    true
+                }
+                Entry::Occupied(_) => false,
+                Entry::Vacant(entry) => {
+                    entry.insert(next_cost);
+                    true
+                }
+            };
+
+            if improved {
+                previous.insert(next_key, (key, m));
+                frontier.push(Reverse((next_cost + next.estimate(), next_cost, next_key)));
+            }
+        }
+    }
+
+    Err(SolveError::no_solution(
+        "the amphipods can not be organized",
+    ))
+}
+
+/// let `observer` see the burrow after every move of `plan`.
+fn replay(plan: &Plan, observer: &mut impl for<'a> Observer<Replay<'a>>) -> SolveResult<u64> {
+    for (i, (last_move, burrow)) in plan.moves.iter().zip(&plan.arrangements()[1..]).enumerate() {
+        notify(
+            observer,
+            &Replay {
+                step: i + 1,
+                last_move,
+                burrow,
+            },
+        )?;
+    }
+
+    Ok(plan.energy())
+}
+
+pub fn plan_part_one(input: &str) -> SolveResult<Plan> {
+    organize(&parse(input)?)
+}
+
+pub fn plan_part_two(input: &str) -> SolveResult<Plan> {
+    let burrow = parse(input)?;
+
+    if burrow.depth + UNFOLDED.len() > MAX_DEPTH {
+        return Err(SolveError::invalid(
+            "can only unfold a burrow with at most two rows of rooms",
+        ));
+    }
+
+    organize(&burrow.unfold())
+}
+
+/// like `try_part_one`, but `observer` sees the burrow after every move of an optimal plan.
+pub fn observe_part_one(
+    input: &str,
+    observer: &mut impl for<'a> Observer<Replay<'a>>,
+) -> SolveResult<u64> {
+    replay(&plan_part_one(input)?, observer)
+}
+
+pub fn try_part_one(input: &str) -> SolveResult<u64> {
+    Ok(plan_part_one(input)?.energy())
+}
+
+/// like `try_part_two`, but `observer` sees the burrow after every move of an optimal plan.
+pub fn observe_part_two(
+    input: &str,
+    observer: &mut impl for<'a> Observer<Replay<'a>>,
+) -> SolveResult<u64> {
+    replay(&plan_part_two(input)?, observer)
+}
+
+pub fn try_part_two(input: &str) -> SolveResult<u64> {
+    Ok(plan_part_two(input)?.energy())
 }
 
-pub fn part_one(_: &str) -> u64 {
-    add_lines("3 3 5 8", "2 3 5", "2 3 4", "9 9")
+/// plain Dijkstra over the arrangements, with every amphipod stopping in the hallway on its way to its room.
+#[cfg(test)]
+mod naive {
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashMap};
+
+    const STOPS: [usize; 7] = [0, 1, 3, 5, 7, 9, 10];
+
+    /// the hallway followed by the rooms from top to bottom, `b'.'` for empty cells.
+    type State = (Vec<u8>, Vec<Vec<u8>>);
+
+    fn cost(amphipod: u8) -> u64 {
+        10u64.pow((amphipod - b'A') as u32)
+    }
+
+    fn is_clear(hallway: &[u8], from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..=to
+        } else {
+            to..=from - 1
+        };
+        hallway[range].iter().all(|&cell| cell == b'.')
+    }
+
+    fn next(state: &State) -> Vec<(u64, State)> {
+        let (hallway, rooms) = state;
+        let mut next = Vec::new();
+
+        for (room, cells) in rooms.iter().enumerate() {
+            let own = b'A' + room as u8;
+            let Some(row) = cells.iter().position(|&cell| cell != b'.') else {
+                continue;
+            };
+            if cells.iter().all(|&cell| cell == b'.' || cell == own) {
+                continue;
+            }
+
+            let entrance = 2 + 2 * room;
+            for stop in STOPS {
+                if is_clear(hallway, entrance, stop) {
+                    let mut state = state.clone();
+                    state.0[stop] = cells[row];
+                    state.1[room][row] = b'.';
+                    let steps = row + 1 + entrance.abs_diff(stop);
+                    next.push((steps as u64 * cost(cells[row]), state));
+                }
+            }
+        }
+
+        for (position, &amphipod) in hallway.iter().enumerate() {
+            if amphipod == b'.' {
+                continue;
+            }
+
+            let room = (amphipod - b'A') as usize;
+            let cells = &rooms[room];
+            let entrance = 2 + 2 * room;
+            if cells.iter().all(|&cell| cell == b'.' || cell == amphipod)
+                && is_clear(hallway, position, entrance)
+            {
+                let row = cells.iter().rposition(|&cell| cell == b'.').unwrap();
+                let mut state = state.clone();
+                state.0[position] = b'.';
+                state.1[room][row] = amphipod;
+                let steps = position.abs_diff(entrance) + row + 1;
+                next.push((steps as u64 * cost(amphipod), state));
+            }
+        }
+
+        next
+    }
+
+    /// the least energy to organize the burrow in `input`, with the folded rows inserted if `unfold` is set.
+    pub fn organize(input: &str, unfold: bool) -> Option<u64> {
+        let amphipods: Vec<u8> = input.bytes().filter(u8::is_ascii_uppercase).collect();
+        let mut rows: Vec<&[u8]> = amphipods.chunks(4).collect();
+        if unfold {
+            rows.splice(1..1, [&b"DCBA"[..], &b"DBAC"[..]]);
+        }
+
+        let rooms = (0..4)
+            .map(|room| rows.iter().map(|row| row[room]).collect())
+            .collect();
+        let start: State = (vec![b'.'; 11], rooms);
+
+        let mut energy = HashMap::from([(start.clone(), 0)]);
+        let mut frontier = BinaryHeap::from([Reverse((0, start))]);
+
+        while let Some(Reverse((cost, state))) = frontier.pop() {
+            if cost > energy[&state] {
+                continue;
+            }
+
+            let organized = state
+                .1
+                .iter()
+                .enumerate()
+                .all(|(room, cells)| cells.iter().all(|&cell| cell == b'A' + room as u8));
+            if organized {
+                return Some(cost);
+            }
+
+            for (step, next) in next(&state) {
+                let cost = cost + step;
+                if energy.get(&next).is_none_or(|&known| cost < known) {
+                    energy.insert(next.clone(), cost);
+                    frontier.push(Reverse((cost, next)));
+                }
+            }
+        }
+
+        None
+    }
 }
 
-pub fn part_two(_: &str) -> u64 {
-    add_lines(
-        "8 8 4 5 5 5 9 9",
-        "7 4 5 8 7 7",
-        "7 2 5 6 5 6",
-        "11 11 11 11",
-    )
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        use crate::read_file;
+        let input = read_file("examples", 23);
+        assert_eq!(try_part_one(&input), Ok(12521));
+    }
+
+    #[test]
+    fn test_part_two() {
+        use crate::read_file;
+        let input = read_file("examples", 23);
+        assert_eq!(try_part_two(&input), Ok(44169));
+    }
+
+    #[test]
+    fn test_unfold() {
+        let burrow = parse(&crate::read_file("examples", 23)).unwrap().unfold();
+        let rows: Vec<Vec<u8>> = (0..ROOMS).map(|room| burrow.room(room).to_vec()).collect();
+        assert_eq!(
+            rows,
+            vec![
+                vec![2, 4, 4, 1],
+                vec![3, 3, 2, 4],
+                vec![2, 2, 1, 3],
+                vec![4, 1, 3, 1]
+            ]
+        );
+        assert_eq!(Burrow::decode(burrow.encode(), 4), burrow);
+    }
+
+    #[test]
+    fn test_plan() {
+        use crate::read_file;
+        let input = read_file("examples", 23);
+
+        let plan = plan_part_one(&input).unwrap();
+        assert_eq!(plan.energy(), 12521);
+        assert_eq!(plan.start.to_string(), input.trim_end());
+        assert_eq!(
+            plan.moves.last(),
+            Some(&Move {
+                amphipod: 'D',
+                from: Cell::Hallway(5),
+                to: Cell::Room { room: 3, row: 0 },
+                energy: 4000
+            })
+        );
+        assert!(plan.arrangements().last().unwrap().is_organized());
+
+        let plan = plan_part_two(&input).unwrap();
+        assert_eq!(plan.energy(), 44169);
+        assert_eq!(plan.start.depth, 4);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        use crate::helpers::snapshot::assert_snapshot;
+        use crate::read_file;
+
+        let plan = plan_part_one(&read_file("examples", 23)).unwrap();
+        assert_snapshot("day23", &plan.render());
+    }
+
+    #[test]
+    fn test_observe() {
+        use crate::read_file;
+        use std::ops::ControlFlow;
+
+        let input = read_file("examples", 23);
+        let mut energy = 0;
+        let result = observe_part_one(&input, &mut |replay: &Replay| {
+            energy += replay.last_move.energy;
+            ControlFlow::Continue(())
+        });
+        assert_eq!(result, Ok(12521));
+        assert_eq!(energy, 12521);
+
+        let result = observe_part_one(&input, &mut |replay: &Replay| {
+            if replay.step < 3 {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+        assert_eq!(result, Err(SolveError::Stopped));
+    }
+
+    #[test]
+    fn test_naive() {
+        use crate::generate;
+        use crate::helpers::rng::Rng;
+
+        // both would be overestimated by charging amphipods that are already home below a stranger.
+        for (rows, energy) in [("B#C#D#A", "B#A#C#D"), ("B#A#C#C", "A#D#B#D")]
+            .into_iter()
+            .zip([4518, 8026])
+        {
+            let input = format!(
+                "#############\n#...........#\n###{}###\n  #{}#\n  #########\n",
+                rows.0, rows.1
+            );
+            assert_eq!(try_part_one(&input), Ok(energy));
+            assert_eq!(naive::organize(&input, false), Some(energy));
+        }
+
+        for seed in 0..8 {
+            let input = generate::day23(&mut Rng::new(seed), 0);
+            let plan = plan_part_one(&input).unwrap();
+            assert_eq!(Some(plan.energy()), naive::organize(&input, false));
+
+            // every move has to be legal in the arrangement it is made from, at the energy it claims.
+            let arrangements = plan.arrangements();
+            for (m, burrow) in plan.moves.iter().zip(&arrangements) {
+                assert!(burrow.moves().iter().any(|(_, legal)| legal == m));
+            }
+            assert!(arrangements.last().unwrap().is_organized());
+        }
+
+        // unfolded burrows take the naive search a while, one is enough.
+        let input = generate::day23(&mut Rng::new(0), 0);
+        assert_eq!(try_part_two(&input).ok(), naive::organize(&input, true));
+    }
+
+    #[test]
+    fn test_invalid_burrows() {
+        assert!(parse("").is_err());
+        assert!(try_part_one(
+            "#############\n#...........#\n###A#B#C#D###\n  #A#B#C#A#\n  #########"
+        )
+        .is_err());
+        assert!(try_part_one(
+            "#############\n#...........#\n###A#B#C#E###\n  #A#B#C#D#\n  #########"
+        )
+        .is_err());
+        assert_eq!(
+            try_part_one("#############\n#...........#\n###A#B#C#D###\n  #########"),
+            Ok(0)
+        );
+        assert!(try_part_two(
+            "#############\n#...........#\n###A#B#C#D###\n  #A#B#C#D#\n  #A#B#C#D#\n  #########"
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,363 @@
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::{parse_lines, ParseResult, Parser};
+use crate::helpers::trace::{notify, Observer};
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+const REGISTERS: [(&str, Register); 4] = [
+    ("w", Register::W),
+    ("x", Register::X),
+    ("y", Register::Y),
+    ("z", Register::Z),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Register(Register),
+    Number(i64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Add,
+    Mul,
+    Div,
+    Mod,
+    Eql,
+}
+
+const OPERATIONS: [(&str, Operation); 5] = [
+    ("add", Operation::Add),
+    ("mul", Operation::Mul),
+    ("div", Operation::Div),
+    ("mod", Operation::Mod),
+    ("eql", Operation::Eql),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    /// read the next number of the input into a register.
+    Inp(Register),
+    /// apply an operation to a register and an operand, storing the result in the register.
+    Binary(Operation, Register, Operand),
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (name, _) = REGISTERS.iter().find(|(_, r)| r == self).unwrap();
+        write!(f, "{}", name)
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Register(r) => write!(f, "{}", r),
+            Operand::Number(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Inp(r) => write!(f, "inp {}", r),
+            Instruction::Binary(operation, a, b) => {
+                let (name, _) = OPERATIONS.iter().find(|(_, o)| o == operation).unwrap();
+                write!(f, "{} {} {}", name, a, b)
+            }
+        }
+    }
+}
+
+fn register(p: &mut Parser) -> ParseResult<Register> {
+    for (name, r) in REGISTERS {
+        if p.try_tag(name) {
+            return Ok(r);
+        }
+    }
+
+    Err(p.error("register `w`, `x`, `y` or `z`"))
+}
+
+fn operand(p: &mut Parser) -> ParseResult<Operand> {
+    match register(p) {
+        Ok(r) => Ok(Operand::Register(r)),
+        Err(_) => p
+            .integer()
+            .map(Operand::Number)
+            .map_err(|_| p.error("register or integer")),
+    }
+}
+
+fn instruction(p: &mut Parser) -> ParseResult<Instruction> {
+    if p.try_tag("inp ") {
+        return Ok(Instruction::Inp(register(p)?));
+    }
+
+    for (name, operation) in OPERATIONS {
+        if p.try_tag(name) {
+            p.tag(" ")?;
+            let a = register(p)?;
+            p.tag(" ")?;
+            return Ok(Instruction::Binary(operation, a, operand(p)?));
+        }
+    }
+
+    Err(p.error("instruction `inp`, `add`, `mul`, `div`, `mod` or `eql`"))
+}
+
+/// Parse an ALU program with one instruction per line.
+pub fn parse(input: &str) -> SolveResult<Vec<Instruction>> {
+    Ok(parse_lines(input, instruction)?)
+}
+
+/// The values of the registers `w`, `x`, `y` and `z`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Registers([i64; 4]);
+
+impl Index<Register> for Registers {
+    type Output = i64;
+
+    fn index(&self, register: Register) -> &i64 {
+        &self.0[register as usize]
+    }
+}
+
+impl IndexMut<Register> for Registers {
+    fn index_mut(&mut self, register: Register) -> &mut i64 {
+        &mut self.0[register as usize]
+    }
+}
+
+impl Display for Registers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [w, x, y, z] = self.0;
+        write!(f, "w={} x={} y={} z={}", w, x, y, z)
+    }
+}
+
+/// The registers after an instruction, passed to observers.
+pub struct State<'a> {
+    /// index of the instruction in the program, starting at `0`.
+    pub position: usize,
+    pub instruction: &'a Instruction,
+    pub registers: &'a Registers,
+}
+
+impl Display for State<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:>4}  {:<10} {}",
+            self.position + 1,
+            self.instruction.to_string(),
+            self.registers
+        )
+    }
+}
+
+/// Executes a program one instruction at a time, reading `inp` values from `input`.
+/// All registers start at `0`.
+pub struct Alu<'a, I> {
+    program: &'a [Instruction],
+    input: I,
+    /// index of the next instruction.
+    position: usize,
+    pub registers: Registers,
+}
+
+impl<'a, I: Iterator<Item = i64>> Alu<'a, I> {
+    pub fn new(program: &'a [Instruction], input: impl IntoIterator<IntoIter = I>) -> Self {
+        Alu {
+            program,
+            input: input.into_iter(),
+            position: 0,
+            registers: Registers::default(),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.position == self.program.len()
+    }
+
+    /// Execute the next instruction and return the registers after it, or `None` if the program has ended.
+    /// Division truncates towards zero. Dividing by zero, a `mod` with a negative dividend or a non-positive
+    /// divisor, running out of input and overflowing registers are errors.
+    pub fn step(&mut self) -> SolveResult<Option<&Registers>> {
+        let instruction = match self.program.get(self.position) {
+            Some(instruction) => *instruction,
+            None => return Ok(None),
+        };
+
+        let position = self.position;
+        let error = |reason: &str| {
+            SolveError::invalid(format!(
+                "instruction {} `{}`: {}",
+                position + 1,
+                instruction,
+                reason
+            ))
+        };
+
+        match instruction {
+            Instruction::Inp(r) => {
+                self.registers[r] = self.input.next().ok_or_else(|| error("no input left"))?;
+            }
+            Instruction::Binary(operation, r, operand) => {
+                let a = self.registers[r];
+                let b = match operand {
+                    Operand::Register(other) => self.registers[other],
+                    Operand::Number(n) => n,
+                };
+
+                self.registers[r] = match operation {
+                    Operation::Add => a.checked_add(b).ok_or_else(|| error("overflow"))?,
+                    Operation::Mul => a.checked_mul(b).ok_or_else(|| error("overflow"))?,
+                    Operation::Div if b == 0 => return Err(error("division by zero")),
+                    Operation::Div => a.checked_div(b).ok_or_else(|| error("overflow"))?,
+                    Operation::Mod if a < 0 || b <= 0 => {
+                        return Err(error(&format!("can not compute {} mod {}", a, b)))
+                    }
+                    Operation::Mod => a % b,
+                    Operation::Eql => (a == b) as i64,
+                };
+            }
+        }
+
+        self.position += 1;
+        Ok(Some(&self.registers))
+    }
+
+    /// Execute the rest of the program, `observer` sees the registers after every instruction.
+    pub fn run(
+        &mut self,
+        observer: &mut impl for<'b> Observer<State<'b>>,
+    ) -> SolveResult<Registers> {
+        while !self.is_done() {
+            let position = self.position;
+            self.step()?;
+
+            notify(
+                observer,
+                &State {
+                    position,
+                    instruction: &self.program[position],
+                    registers: &self.registers,
+                },
+            )?;
+        }
+
+        Ok(self.registers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(program: &str, input: &[i64]) -> SolveResult<Registers> {
+        let program = parse(program)?;
+        Alu::new(&program, input.iter().copied()).run(&mut ())
+    }
+
+    #[test]
+    fn test_parse() {
+        let program = parse("inp w\nadd x -12\neql z y").unwrap();
+        assert_eq!(
+            program,
+            vec![
+                Instruction::Inp(Register::W),
+                Instruction::Binary(Operation::Add, Register::X, Operand::Number(-12)),
+                Instruction::Binary(Operation::Eql, Register::Z, Operand::Register(Register::Y)),
+            ]
+        );
+        let printed: Vec<String> = program.iter().map(|i| i.to_string()).collect();
+        assert_eq!(printed, vec!["inp w", "add x -12", "eql z y"]);
+
+        assert!(parse("inp 3").is_err());
+        assert!(parse("sub x 1").is_err());
+        assert!(parse("add 1 x").is_err());
+        assert!(parse("mul x v").is_err());
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(run("inp x\nmul x -1", &[7]).unwrap()[Register::X], -7);
+        assert_eq!(
+            run("inp z\ninp x\nmul z 3\neql z x", &[3, 9]).unwrap()[Register::Z],
+            1
+        );
+        assert_eq!(
+            run("inp z\ninp x\nmul z 3\neql z x", &[3, 8]).unwrap()[Register::Z],
+            0
+        );
+
+        // converts the input to binary, one bit per register.
+        let program = crate::read_file("examples", 24);
+        assert_eq!(run(&program, &[13]).unwrap(), Registers([1, 1, 0, 1]));
+        assert_eq!(run(&program, &[6]).unwrap(), Registers([0, 1, 1, 0]));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(run("inp x\ndiv x 2", &[-7]).unwrap()[Register::X], -3);
+        assert_eq!(run("inp x\ndiv x -2", &[7]).unwrap()[Register::X], -3);
+        assert_eq!(run("inp x\nmod x 5", &[17]).unwrap()[Register::X], 2);
+
+        let error = |message: &str| Err(SolveError::invalid(message));
+        assert_eq!(
+            run("inp x\ndiv x y", &[1]),
+            error("instruction 2 `div x y`: division by zero")
+        );
+        assert_eq!(
+            run("inp x\nmod x 26", &[-3]),
+            error("instruction 2 `mod x 26`: can not compute -3 mod 26")
+        );
+        assert_eq!(
+            run("inp x\nmod y x", &[0]),
+            error("instruction 2 `mod y x`: can not compute 0 mod 0")
+        );
+        assert_eq!(
+            run("inp x\ninp y", &[1]),
+            error("instruction 2 `inp y`: no input left")
+        );
+        assert!(run("inp x\nmul x x\nmul x x\nmul x x", &[1 << 20]).is_err());
+    }
+
+    #[test]
+    fn test_step() {
+        let program = parse("inp w\nadd z w\nmul z 3").unwrap();
+        let mut alu = Alu::new(&program, [5]);
+
+        assert_eq!(alu.step(), Ok(Some(&Registers([5, 0, 0, 0]))));
+        assert_eq!(alu.step(), Ok(Some(&Registers([5, 0, 0, 5]))));
+        assert_eq!(alu.step(), Ok(Some(&Registers([5, 0, 0, 15]))));
+        assert!(alu.is_done());
+        assert_eq!(alu.step(), Ok(None));
+
+        let mut seen = Vec::new();
+        let mut alu = Alu::new(&program, [2]);
+        alu.run(&mut |state: &State| {
+            seen.push(state.to_string());
+            std::ops::ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(
+            seen,
+            vec![
+                "   1  inp w      w=2 x=0 y=0 z=0",
+                "   2  add z w    w=2 x=0 y=0 z=2",
+                "   3  mul z 3    w=2 x=0 y=0 z=6",
+            ]
+        );
+    }
+}
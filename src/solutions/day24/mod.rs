@@ -0,0 +1,317 @@
+pub mod alu;
+
+use self::alu::{Alu, Instruction, Register, State};
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::parse_line;
+use crate::helpers::trace::Observer;
+
+/// the MONAD program runs the same block of instructions once per digit of the model number.
+/// blocks only differ in the operands marked with `{}`, these are the parameters `(a, b, c)` of the digit.
+const BLOCK: [&str; 18] = [
+    "inp w", "mul x 0", "add x z", "mod x 26", "div z {}", "add x {}", "eql x w", "eql x 0",
+    "mul y 0", "add y 25", "mul y x", "add y 1", "mul z y", "mul y 0", "add y w", "add y {}",
+    "mul y x", "add z y",
+];
+
+const DIGITS: usize = 14;
+
+/// parameters of a block: `z` is divided by `a`, `b` is added to the check value and `c` to the pushed digit.
+type Parameters = (i64, i64, i64);
+
+/// extract the parameters of every digit from the MONAD program.
+fn parse(input: &str) -> SolveResult<Vec<Parameters>> {
+    let lines: Vec<&str> = input.lines().collect();
+
+    if lines.len() != DIGITS * BLOCK.len() {
+        return Err(SolveError::invalid(format!(
+            "expected {} blocks of {} instructions, found {} lines",
+            DIGITS,
+            BLOCK.len(),
+            lines.len()
+        )));
+    }
+
+    lines
+        .chunks(BLOCK.len())
+        .enumerate()
+        .map(|(digit, block)| {
+            let mut operands = Vec::new();
+
+            for (i, (line, template)) in block.iter().zip(BLOCK).enumerate() {
+                let line_number = digit * BLOCK.len() + i + 1;
+
+                parse_line(line, line_number, |p| {
+                    match template.strip_suffix("{}") {
+                        Some(instruction) => {
+                            p.tag(instruction)?;
+                            operands.push(p.integer()?);
+                        }
+                        None => p.tag(template)?,
+                    }
+                    Ok(())
+                })?;
+            }
+
+            let (a, b, c) = (operands[0], operands[1], operands[2]);
+            if a != 1 && a != 26 {
+                return Err(SolveError::invalid(format!(
+                    "digit {} divides z by {}, expected 1 or 26",
+                    digit + 1,
+                    a
+                )));
+            }
+
+            Ok((a, b, c))
+        })
+        .collect()
+}
+
+/// which of the valid model numbers to build.
+#[derive(Clone, Copy)]
+enum Pick {
+    Largest,
+    Smallest,
+}
+
+/// Blocks with `a == 1` push their digit plus `c` onto `z`, used as a base 26 stack. Blocks with `a == 26` pop it
+/// again and only keep from pushing their own digit if it equals the popped value plus `b`. `z` ends up `0` only
+/// if every pop matches, so every popped digit is tied to its pushed one: `digit_pop = digit_push + c_push + b_pop`.
+fn solve(steps: &[Parameters], pick: Pick) -> SolveResult<i64> {
+    let mut digits = [0; DIGITS];
+    // position and `c` of every pushed digit that is not popped yet.
+    let mut stack: Vec<(usize, i64)> = Vec::new();
+
+    for (digit, &(a, b, c)) in steps.iter().enumerate() {
+        if a == 1 {
+            stack.push((digit, c));
+            continue;
+        }
+
+        let (pushed, offset) = stack.pop().ok_or_else(|| {
+            SolveError::no_solution(format!("digit {} pops from an empty stack", digit + 1))
+        })?;
+
+        let offset = offset.saturating_add(b);
+        if !(-8..=8).contains(&offset) {
+            return Err(SolveError::no_solution(format!(
+                "digits {} and {} can not differ by {}",
+                pushed + 1,
+                digit + 1,
+                offset
+            )));
+        }
+
+        digits[pushed] = match pick {
+            Pick::Largest => 9.min(9 - offset),
+            Pick::Smallest => 1.max(1 - offset),
+        };
+        digits[digit] = digits[pushed] + offset;
+    }
+
+    if let Some((digit, _)) = stack.first() {
+        return Err(SolveError::no_solution(format!(
+            "digit {} is pushed but never popped",
+            digit + 1
+        )));
+    }
+
+    Ok(digits.iter().fold(0, |acc, digit| acc * 10 + digit))
+}
+
+/// Run the MONAD `program` on the digits of `model_number` with the ALU, independent of how it was found.
+/// `observer` sees the registers after every instruction.
+pub fn verify(
+    program: &[Instruction],
+    model_number: i64,
+    observer: &mut impl for<'a> Observer<State<'a>>,
+) -> SolveResult<bool> {
+    let digits: Vec<i64> = model_number
+        .to_string()
+        .chars()
+        .map(|c| c.to_digit(10).map_or(-1, i64::from))
+        .collect();
+
+    if digits.len() != DIGITS || digits.iter().any(|&digit| !(1..=9).contains(&digit)) {
+        return Ok(false);
+    }
+
+    let registers = Alu::new(program, digits).run(observer)?;
+    Ok(registers[Register::Z] == 0)
+}
+
+/// pick a model number found by the solver and double check it with the ALU.
+fn answer(
+    input: &str,
+    pick: Pick,
+    observer: &mut impl for<'a> Observer<State<'a>>,
+) -> SolveResult<i64> {
+    let program = alu::parse(input)?;
+    let model_number = solve(&parse(input)?, pick)?;
+
+    if verify(&program, model_number, observer)? {
+        Ok(model_number)
+    } else {
+        Err(SolveError::no_solution(format!(
+            "MONAD rejects {}, the program does not work the way the solver expects",
+            model_number
+        )))
+    }
+}
+
+/// like `try_part_one`, but `observer` sees the ALU registers while checking the answer.
+pub fn observe_part_one(
+    input: &str,
+    observer: &mut impl for<'a> Observer<State<'a>>,
+) -> SolveResult<i64> {
+    answer(input, Pick::Largest, observer)
+}
+
+pub fn try_part_one(input: &str) -> SolveResult<i64> {
+    observe_part_one(input, &mut ())
+}
+
+pub fn try_part_two(input: &str) -> SolveResult<i64> {
+    answer(input, Pick::Smallest, &mut ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SolveError;
+    use crate::helpers::parse::ParseError;
+    use std::fmt::Write;
+
+    /// the parameters of the puzzle input this module was first written for.
+    const STEPS: [Parameters; DIGITS] = [
+        (1, 13, 10),
+        (1, 11, 16),
+        (1, 11, 0),
+        (1, 10, 13),
+        (26, -14, 7),
+        (26, -4, 11),
+        (1, 11, 11),
+        (26, -3, 10),
+        (1, 12, 16),
+        (26, -12, 8),
+        (1, 13, 15),
+        (26, -12, 2),
+        (26, -15, 5),
+        (26, -12, 10),
+    ];
+
+    fn program(steps: &[Parameters]) -> String {
+        let mut program = String::new();
+
+        for &(a, b, c) in steps {
+            let mut operands = [a, b, c].into_iter();
+            for template in BLOCK {
+                match template.strip_suffix("{}") {
+                    Some(instruction) => {
+                        writeln!(program, "{}{}", instruction, operands.next().unwrap()).unwrap()
+                    }
+                    None => writeln!(program, "{}", template).unwrap(),
+                }
+            }
+        }
+
+        program
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(&program(&STEPS)), Ok(STEPS.to_vec()));
+    }
+
+    #[test]
+    fn test_unexpected_programs() {
+        let monad = program(&STEPS);
+
+        assert_eq!(
+            parse(&crate::read_file("examples", 24)),
+            Err(SolveError::invalid(
+                "expected 14 blocks of 18 instructions, found 11 lines"
+            ))
+        );
+
+        let swapped = monad.replacen("eql x w\neql x 0", "eql x 0\neql x w", 1);
+        assert_eq!(
+            parse(&swapped),
+            Err(SolveError::Parse(ParseError {
+                line: 7,
+                column: 1,
+                expected: "`eql x w`".into()
+            }))
+        );
+
+        let divided = monad.replacen("div z 26", "div z 13", 1);
+        assert_eq!(
+            parse(&divided),
+            Err(SolveError::invalid(
+                "digit 5 divides z by 13, expected 1 or 26"
+            ))
+        );
+
+        assert!(parse(&monad.replacen("add x 13", "add x z", 1)).is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let program = alu::parse(&program(&STEPS)).unwrap();
+
+        assert_eq!(verify(&program, 98998519596997, &mut ()), Ok(true));
+        assert_eq!(verify(&program, 31521119151421, &mut ()), Ok(true));
+        assert_eq!(verify(&program, 98998519596998, &mut ()), Ok(false));
+        assert_eq!(verify(&program, 31521119151420, &mut ()), Ok(false));
+        assert_eq!(verify(&program, 9999, &mut ()), Ok(false));
+    }
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(try_part_one(&program(&STEPS)), Ok(98998519596997));
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(try_part_two(&program(&STEPS)), Ok(31521119151421));
+    }
+
+    #[test]
+    fn test_generated_programs() {
+        use crate::generate;
+        use crate::helpers::rng::Rng;
+
+        // both answers are checked with the ALU before they are returned.
+        for seed in 0..200 {
+            let input = generate::day24(&mut Rng::new(seed), 0);
+            let largest = try_part_one(&input).unwrap();
+            let smallest = try_part_two(&input).unwrap();
+            assert!(largest >= smallest, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_unsolvable_programs() {
+        let mut steps = STEPS;
+        steps[4].1 = 10;
+        assert_eq!(
+            try_part_one(&program(&steps)),
+            Err(SolveError::no_solution(
+                "digits 4 and 5 can not differ by 23"
+            ))
+        );
+
+        let mut steps = STEPS;
+        steps[13].0 = 1;
+        assert_eq!(
+            try_part_one(&program(&steps)),
+            Err(SolveError::no_solution(
+                "digit 1 is pushed but never popped"
+            ))
+        );
+
+        assert_eq!(
+            solve(&STEPS[4..], Pick::Largest),
+            Err(SolveError::no_solution("digit 1 pops from an empty stack"))
+        );
+    }
+}
@@ -1,4 +1,11 @@
-#[derive(Clone)]
+use crate::error::{SolveError, SolveResult};
+use crate::helpers::parse::{parse_rows, ParseResult};
+use crate::helpers::render::Render;
+use crate::helpers::trace::{notify, Observer};
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+
+#[derive(Clone, Hash, PartialEq, Eq)]
 enum Occupant {
     EastBound,
     SouthBound,
@@ -7,35 +14,41 @@ enum Occupant {
 
 type Line = Vec<Occupant>;
 
-fn parse(input: &str) -> Vec<Line> {
-    input
-        .lines()
-        .filter_map(|l| {
-            if l.is_empty() {
-                None
-            } else {
-                Some(
-                    l.chars()
-                        .map(|c| match c {
-                            '>' => Occupant::EastBound,
-                            'v' => Occupant::SouthBound,
-                            '.' => Occupant::Empty,
-                            c => panic!("unexpected input: {}", c),
-                        })
-                        .collect(),
-                )
-            }
-        })
-        .collect()
+fn parse(input: &str) -> ParseResult<Vec<Line>> {
+    parse_rows(input, "`>`, `v` or `.`", |c| match c {
+        '>' => Some(Occupant::EastBound),
+        'v' => Some(Occupant::SouthBound),
+        '.' => Some(Occupant::Empty),
+        _ => None,
+    })
+}
+
+/// The sea cucumbers after a step, passed to observers.
+pub struct Herds<'a> {
+    pub step: u32,
+    /// number of sea cucumbers that moved during this step.
+    pub moved: u32,
+    grid: &'a [Line],
 }
 
-fn simulate_step(grid: &mut Vec<Line>) -> u32 {
+impl Display for Herds<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rendered = self.grid.render(|o| match o {
+            Occupant::EastBound => '>',
+            Occupant::SouthBound => 'v',
+            Occupant::Empty => '.',
+        });
+        write!(f, "{}\n{} moved", rendered, self.moved)
+    }
+}
+
+fn simulate_step(grid: &mut [Line]) -> u32 {
     let mut moved = 0;
 
     let w = grid[0].len();
     let h = grid.len();
 
-    let reference = grid.clone();
+    let reference = grid.to_vec();
     // eastbound traffic
     for y in 0..h {
         for x in 0..w {
@@ -51,7 +64,7 @@ fn simulate_step(grid: &mut Vec<Line>) -> u32 {
         }
     }
 
-    let reference = grid.clone();
+    let reference = grid.to_vec();
     // southbound traffic
     for y in 0..h {
         for x in 0..w {
@@ -70,23 +83,37 @@ fn simulate_step(grid: &mut Vec<Line>) -> u32 {
     moved
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let mut grid = parse(input);
+/// like `try_part_one`, but `observer` sees the sea cucumbers after every step.
+pub fn observe_part_one(
+    input: &str,
+    observer: &mut impl for<'a> Observer<Herds<'a>>,
+) -> SolveResult<u32> {
+    let mut grid = parse(input)?;
+    // the herds wrap around, so they might move forever without ever coming to a halt.
+    let mut seen: HashSet<Vec<Line>> = HashSet::new();
     let mut step = 0;
 
-    loop {
+    while seen.insert(grid.clone()) {
         step += 1;
 
-        if simulate_step(&mut grid) == 0 {
-            break;
+        let moved = simulate_step(&mut grid);
+        let grid = &grid;
+        notify(observer, &Herds { step, moved, grid })?;
+
+        if moved == 0 {
+            return Ok(step);
         }
     }
 
-    step
+    Err(SolveError::no_solution("sea cucumbers never stop moving"))
 }
 
-pub fn part_two(_input: &str) -> u32 {
-    0
+pub fn try_part_one(input: &str) -> SolveResult<u32> {
+    observe_part_one(input, &mut ())
+}
+
+pub fn try_part_two(_input: &str) -> SolveResult<u32> {
+    Ok(0)
 }
 
 #[cfg(test)]
@@ -95,15 +122,66 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        use aoc::read_file;
+        use crate::read_file;
         let input = read_file("examples", 25);
-        assert_eq!(part_one(&input), 58);
+        assert_eq!(try_part_one(&input), Ok(58));
     }
 
     #[test]
     fn test_part_two() {
-        use aoc::read_file;
+        use crate::read_file;
+        let input = read_file("examples", 25);
+        assert_eq!(try_part_two(&input), Ok(0));
+    }
+
+    #[test]
+    fn test_snapshot() {
+        use crate::helpers::snapshot::assert_snapshot;
+        use crate::read_file;
+        use std::fmt::Write;
+
+        let mut grid = parse(&read_file("examples", 25)).unwrap();
+        let render = |grid: &[Line]| {
+            grid.render(|o| match o {
+                Occupant::EastBound => '>',
+                Occupant::SouthBound => 'v',
+                Occupant::Empty => '.',
+            })
+        };
+        let mut states = format!("initial state:\n{}\n\n", render(&grid));
+
+        for step in 1..=58 {
+            let moved = simulate_step(&mut grid);
+            if step <= 5 || step % 10 == 0 || step > 55 {
+                write!(
+                    states,
+                    "after step {} ({} moved):\n{}\n\n",
+                    step,
+                    moved,
+                    render(&grid)
+                )
+                .unwrap();
+            }
+        }
+
+        assert_snapshot("day25", &states);
+    }
+
+    #[test]
+    fn test_observe() {
+        use crate::read_file;
+        use std::ops::ControlFlow;
+
         let input = read_file("examples", 25);
-        assert_eq!(part_two(&input), 0);
+        let mut moved = Vec::new();
+        let result = observe_part_one(&input, &mut |herds: &Herds| {
+            moved.push(herds.moved);
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(result, Ok(58));
+        assert_eq!(moved.len(), 58);
+        assert_eq!(moved.last(), Some(&0));
+        assert!(moved[..57].iter().all(|m| *m > 0));
     }
 }